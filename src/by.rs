@@ -39,3 +39,19 @@ unsafe impl RefOrVal for ()
     #[inline(always)] fn into_v(self) -> Self::V { self }
     #[inline(always)] unsafe fn from_v(_: Self::V) -> Self { () }
 }
+
+
+pub trait IntoOwned : RefOrVal
+{
+    fn into_owned(self) -> Self::RAW;
+}
+
+impl<V: Clone> IntoOwned for Ref<V>
+{
+    #[inline(always)] fn into_owned(self) -> V { self.as_ref().clone() }
+}
+
+impl<V> IntoOwned for Val<V>
+{
+    #[inline(always)] fn into_owned(self) -> V { self.0 }
+}
@@ -11,6 +11,38 @@ pub struct RxError
 }
 
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceError
+{
+    NoElements,
+    MoreThanOneElement,
+    ArgumentOutOfRange,
+}
+
+impl Display for SequenceError
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result
+    {
+        match self {
+            SequenceError::NoElements => write!(f, "sequence contains no elements"),
+            SequenceError::MoreThanOneElement => write!(f, "sequence contains more than one element"),
+            SequenceError::ArgumentOutOfRange => write!(f, "argument out of range"),
+        }
+    }
+}
+
+impl Error for SequenceError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeoutError;
+
+impl Display for TimeoutError
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result { write!(f, "timeout") }
+}
+
+impl Error for TimeoutError {}
+
 impl Display for RxError
 {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result
@@ -59,6 +91,20 @@ impl RxError
 
     }
 
+    pub fn sequence(e: SequenceError) -> Self { Self::new(e) }
+
+    pub fn sequence_error(&self) -> Option<SequenceError>
+    {
+        self.err.downcast_ref::<SequenceError>().map(|e| *e)
+    }
+
+    pub fn timeout() -> Self { Self::new(TimeoutError) }
+
+    pub fn timeout_error(&self) -> Option<TimeoutError>
+    {
+        self.err.downcast_ref::<TimeoutError>().map(|e| *e)
+    }
+
     pub fn handle(mut self, f: impl Fn(&Error) -> Option<RxError>) -> Option<RxError>
     {
         let out = f(self.err.as_ref());
@@ -0,0 +1,207 @@
+use crate::*;
+use std::sync::Arc;
+use std::cell::Cell;
+use std::cell::UnsafeCell;
+
+pub struct Concat<'s, 'o, SS:YesNo, By: RefOrVal>
+{
+    obs: Vec<DynObservable<'s, 'o, SS, By>>
+}
+
+impl<'s, 'o, SS:YesNo, By: RefOrVal> Concat<'s, 'o, SS, By>
+{
+    pub fn new(obs: impl Into<Vec<DynObservable<'s, 'o, SS, By>>>) -> Self
+    {
+        Concat{ obs: obs.into() }
+    }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct ConcatState<'s, 'o, SS:YesNo, By: RefOrVal, N, EC>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    obs: Vec<DynObservable<'s, 'o, SS, By>>,
+    index: Cell<usize>,
+    current: UnsafeCell<Unsub<'o, SS>>,
+    looping: Cell<bool>,
+    pending: Cell<bool>,
+}
+
+impl<'s, 'o, SS:YesNo, By: RefOrVal, N, EC: ActEc<'o, SS>> ConcatState<'s, 'o, SS, By, N, EC>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+}
+
+//a source that completes synchronously asks for the next one while it's still subscribing:
+//it's only flagged as `pending` and picked up by the loop, so the stack doesn't grow with each source
+fn subscribe_next<'s: 'o, 'o, SS:YesNo, By: RefOrVal+'o, N: ActNext<'o, SS, By>, EC: ActEc<'o, SS>>
+(state: &Arc<AnySendSync<ConcatState<'s, 'o, SS, By, N, EC>>>)
+{
+    if state.looping.get() {
+        state.pending.replace(true);
+        return;
+    }
+
+    state.looping.replace(true);
+    loop {
+        state.pending.replace(false);
+
+        let i = state.index.get();
+        if i == state.obs.len() {
+            state.end(None);
+            break;
+        }
+        state.index.replace(i + 1);
+
+        let current = Unsub::new();
+        unsafe{ ::std::mem::replace(&mut *state.current.get(), current.clone()) }.unsub();
+
+        current.add(state.obs[i].subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: By| {
+                state.sub.if_not_done(|| state.next.call(v.into_v()));
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec((SSWrap::new(state.clone()), current.clone()), |(state, current), e: Option<RxError>| {
+                state.sub.if_not_done(|| {
+                    if current.is_done() { return; }
+                    current.unsub();
+
+                    if e.is_some() { state.end(e); }
+                    else { subscribe_next(&*state); }
+                });
+            })
+        ));
+
+        if !state.pending.get() || state.sub.is_done() { break; }
+    }
+    state.looping.replace(false);
+}
+
+impl<'s: 'o, 'o, SS:YesNo, By: RefOrVal+'o>
+Observable<'o, SS, By>
+for Concat<'s, 'o, SS, By>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, By>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::<SS>::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(ConcatState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), obs: self.obs.clone(), index: Cell::new(0),
+            current: UnsafeCell::new(Unsub::done()), looping: Cell::new(false), pending: Cell::new(false)
+        }) });
+
+        sub.add(Unsub::with(forward_act_once(SSWrap::new(state.clone()), |state, ()| {
+            unsafe{ &*state.current.get() }.unsub();
+        })));
+
+        sub.if_not_done(|| subscribe_next(&state));
+
+        sub
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, By>>, err_or_comp: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, err_or_comp) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use std::rc::Rc;
+    use crate::util::clones::Clones;
+    use std::cell::RefCell;
+    use std::time::Duration;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let (r1, r2, r3) = Rc::new(RefCell::new(String::new())).clones();
+
+        let a = Of::<NO, i32>::value(1).map(|v:&_| *v).into_dyn();
+        let b = iter_clone(2..4).into_dyn();
+        let c = Of::<NO, i32>::value(4).map(|v:&_| *v).into_dyn();
+
+        Concat::new(vec![a, b, c]).subscribe(move |v| {
+            r1.borrow_mut().push_str(&format!("{}", v));
+        }, move |_| {
+            r2.borrow_mut().push_str("ok");
+        });
+
+        assert_eq!("1234ok", r3.borrow().as_str());
+    }
+
+    #[test]
+    fn sequential()
+    {
+        let out = RefCell::new(String::new());
+        let (a, a1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (b, b1) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        Concat::new(vec![a.into_dyn(), b.into_dyn()]).subscribe(
+            |v:&_| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        b1.next(1);
+        a1.next(2);
+        a1.complete();
+        b1.next(3);
+        assert_eq!(out.borrow().as_str(), "23");
+
+        b1.complete();
+        assert_eq!(out.borrow().as_str(), "23ok");
+    }
+
+    #[test]
+    fn trampoline()
+    {
+        let n = ::std::cell::Cell::new(0);
+        let done = ::std::cell::Cell::new(false);
+        let obs: Vec<_> = (0..100_000).map(|_| Of::<NO, i32>::value(1).into_dyn()).collect();
+
+        Concat::new(obs).subscribe(|v: &i32| { n.replace(n.get() + *v); }, |_e| { done.replace(true); });
+
+        assert_eq!(n.get(), 100_000);
+        assert!(done.get());
+    }
+
+    #[test]
+    fn error()
+    {
+        let out = RefCell::new(String::new());
+        let it = Rc::new(iter_once(0..2));
+        it.subscribe(|_v|{}, ());
+
+        Concat::new(vec![iter_clone(0..2).into_dyn(), it.into_dyn(), iter_clone(0..2).into_dyn()]).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |e: Option<RxError>| out.borrow_mut().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" })
+        );
+
+        assert_eq!(out.borrow().as_str(), "01err");
+    }
+
+    #[test]
+    fn asynced()
+    {
+        let (out, out1, out2) = Arc::new(Mutex::new(String::new())).clones();
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+
+        let a = Timer::new(Duration::from_millis(10), sch.clone()).take(3).into_dyn();
+        let b = Of::<YES, usize>::value(9).map(|v:&_| *v).into_dyn();
+        let c = Timer::new(Duration::from_millis(5), sch.clone()).take(2).into_dyn();
+
+        Concat::new(vec![a, b, c]).subscribe(
+            move |v| out.lock().unwrap().push_str(&format!("{}", v)),
+            move |_e| out1.lock().unwrap().push_str("ok")
+        );
+
+        ::std::thread::sleep(Duration::from_millis(250));
+        assert_eq!(out2.lock().unwrap().as_str(), "012901ok");
+    }
+}
@@ -2,10 +2,14 @@ mod of;
 mod timer;
 mod iter;
 mod merge;
+mod concat;
+mod zip;
 
 pub use self::of::*;
 pub use self::timer::*;
 pub use self::iter::*;
 pub use self::merge::*;
+pub use self::concat::*;
+pub use self::zip::*;
 
 //todo: provide a fasade for all facs ...
\ No newline at end of file
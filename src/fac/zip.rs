@@ -0,0 +1,314 @@
+use crate::*;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+use std::collections::VecDeque;
+
+pub struct Zip<'s, 'o, SS:YesNo, ByA: RefOrVal, ByB: RefOrVal>
+{
+    a: DynObservable<'s, 'o, SS, ByA>,
+    b: DynObservable<'s, 'o, SS, ByB>,
+}
+
+impl<'s, 'o, SS:YesNo, ByA: RefOrVal, ByB: RefOrVal> Zip<'s, 'o, SS, ByA, ByB>
+{
+    pub fn new(a: DynObservable<'s, 'o, SS, ByA>, b: DynObservable<'s, 'o, SS, ByB>) -> Self
+    {
+        Zip{ a, b }
+    }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct ZipState<'o, SS:YesNo, N, EC, A, B>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    a: UnsafeCell<(VecDeque<A>, bool)>,
+    b: UnsafeCell<(VecDeque<B>, bool)>,
+}
+
+impl<'o, SS:YesNo, N: ActNext<'o, SS, Val<(A, B)>>, EC: ActEc<'o, SS>, A, B> ZipState<'o, SS, N, EC, A, B>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+
+    fn end_if_drained(&self)
+    {
+        let (a, b) = unsafe{ (&*self.a.get(), &*self.b.get()) };
+        if (a.1 && a.0.is_empty()) || (b.1 && b.0.is_empty()) {
+            self.end(None);
+        }
+    }
+
+    fn next_a(&self, v: A)
+    {
+        match unsafe{ &mut *self.b.get() }.0.pop_front() {
+            Some(b) => {
+                self.next.call((v, b));
+                self.end_if_drained();
+            },
+            None => unsafe{ &mut *self.a.get() }.0.push_back(v)
+        }
+    }
+
+    fn next_b(&self, v: B)
+    {
+        match unsafe{ &mut *self.a.get() }.0.pop_front() {
+            Some(a) => {
+                self.next.call((a, v));
+                self.end_if_drained();
+            },
+            None => unsafe{ &mut *self.b.get() }.0.push_back(v)
+        }
+    }
+}
+
+impl<'s, 'o, SS:YesNo, ByA: IntoOwned+'o, ByB: IntoOwned+'o>
+Observable<'o, SS, Val<(ByA::RAW, ByB::RAW)>>
+for Zip<'s, 'o, SS, ByA, ByB>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<(ByA::RAW, ByB::RAW)>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::<SS>::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(ZipState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)),
+            a: UnsafeCell::new((VecDeque::new(), false)), b: UnsafeCell::new((VecDeque::new(), false))
+        }) });
+
+        sub.add(self.a.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: ByA| {
+                state.sub.if_not_done(|| state.next_a(v.into_owned()));
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state.clone()), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| {
+                    if e.is_some() { return state.end(e); }
+                    unsafe{ &mut *state.a.get() }.1 = true;
+                    state.end_if_drained();
+                });
+            })
+        ));
+
+        if sub.is_done() { return sub; }
+
+        sub.clone().added(self.b.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: ByB| {
+                state.sub.if_not_done(|| state.next_b(v.into_owned()));
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| {
+                    if e.is_some() { return state.end(e); }
+                    unsafe{ &mut *state.b.get() }.1 = true;
+                    state.end_if_drained();
+                });
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<(ByA::RAW, ByB::RAW)>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+//zips any number of sources of the same type, emitting one `Vec` per index
+pub struct ZipAll<'s, 'o, SS:YesNo, By: RefOrVal>
+{
+    obs: Vec<DynObservable<'s, 'o, SS, By>>
+}
+
+impl<'s, 'o, SS:YesNo, By: RefOrVal> ZipAll<'s, 'o, SS, By>
+{
+    pub fn new(obs: impl Into<Vec<DynObservable<'s, 'o, SS, By>>>) -> Self
+    {
+        ZipAll{ obs: obs.into() }
+    }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct ZipAllState<'o, SS:YesNo, N, EC, V>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    queues: UnsafeCell<Vec<(VecDeque<V>, bool)>>,
+}
+
+impl<'o, SS:YesNo, N: ActNext<'o, SS, Val<Vec<V>>>, EC: ActEc<'o, SS>, V> ZipAllState<'o, SS, N, EC, V>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+
+    fn end_if_drained(&self)
+    {
+        if unsafe{ &*self.queues.get() }.iter().any(|(q, done)| *done && q.is_empty()) {
+            self.end(None);
+        }
+    }
+
+    fn push(&self, index: usize, v: V)
+    {
+        let queues = unsafe{ &mut *self.queues.get() };
+        queues[index].0.push_back(v);
+
+        if queues.iter().all(|(q, _)| !q.is_empty()) {
+            let vs = queues.iter_mut().map(|(q, _)| q.pop_front().unwrap()).collect();
+            self.next.call(vs);
+            self.end_if_drained();
+        }
+    }
+}
+
+impl<'s, 'o, SS:YesNo, By: IntoOwned+'o>
+Observable<'o, SS, Val<Vec<By::RAW>>>
+for ZipAll<'s, 'o, SS, By>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<Vec<By::RAW>>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        if self.obs.is_empty() {
+            ec.call_once(None);
+            return Unsub::done();
+        }
+
+        let sub = Unsub::<SS>::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(ZipAllState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)),
+            queues: UnsafeCell::new(self.obs.iter().map(|_| (VecDeque::new(), false)).collect())
+        }) });
+
+        for (index, o) in self.obs.iter().enumerate() {
+            if sub.is_done() { break; }
+
+            sub.add(o.subscribe(
+                forward_next((), SSWrap::new((state.clone(), index)), |(), caps, v: By| {
+                    let (state, index) = &**caps;
+                    state.sub.if_not_done(|| state.push(*index, v.into_owned()));
+                }, |(), caps| caps.0.sub.is_done() || caps.0.next.stopped()),
+
+                forward_ec(SSWrap::new((state.clone(), index)), |caps, e: Option<RxError>| {
+                    let (state, index) = caps.into_inner();
+                    state.sub.if_not_done(|| {
+                        if e.is_some() { return state.end(e); }
+                        let queues = unsafe{ &mut *state.queues.get() };
+                        queues[index].1 = true;
+                        state.end_if_drained();
+                    });
+                })
+            ));
+        }
+
+        sub
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<Vec<By::RAW>>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use std::rc::Rc;
+    use crate::util::clones::Clones;
+    use std::cell::RefCell;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+        let (a, a1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (b, b1) = Rc::new(Subject::<NO, String>::new()).clones();
+
+        Zip::new(a.into_dyn(), b.into_dyn()).subscribe(
+            |(a, b): (i32, String)| out.borrow_mut().push_str(&format!("{}{},", a, b)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        a1.next(1);
+        a1.next(2);
+        b1.next("a".to_owned());
+        a1.next(3);
+        a1.complete();
+        b1.next("b".to_owned());
+        assert_eq!(out.borrow().as_str(), "1a,2b,");
+
+        b1.next("c".to_owned());
+        assert_eq!(out.borrow().as_str(), "1a,2b,3c,ok");
+    }
+
+    #[test]
+    fn sync()
+    {
+        let out = RefCell::new(String::new());
+
+        Zip::new(iter_clone(0..5).into_dyn(), Of::value(9).into_dyn()).subscribe(
+            |(a, b)| out.borrow_mut().push_str(&format!("{}{},", a, b)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        assert_eq!(out.borrow().as_str(), "09,ok");
+    }
+
+    #[test]
+    fn three()
+    {
+        let out = RefCell::new(String::new());
+        let (a, a1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (b, b1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (c, c1) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        ZipAll::new(vec![a.into_dyn(), b.into_dyn(), c.into_dyn()]).subscribe(
+            |vs: Vec<i32>| out.borrow_mut().push_str(&format!("{:?}", vs)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        a1.next(1);
+        b1.next(2);
+        a1.next(4);
+        assert_eq!(out.borrow().as_str(), "");
+
+        c1.next(3);
+        c1.complete();
+        assert_eq!(out.borrow().as_str(), "[1, 2, 3]ok");
+    }
+
+    #[test]
+    fn empty()
+    {
+        let out = RefCell::new(String::new());
+
+        ZipAll::<NO, Val<i32>>::new(vec![]).subscribe(
+            |vs: Vec<i32>| out.borrow_mut().push_str(&format!("{:?}", vs)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        assert_eq!(out.borrow().as_str(), "ok");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1, out2) = Arc::new(Mutex::new(String::new())).clones();
+        let (a, a1) = Arc::new(Subject::<YES, i32>::new()).clones();
+        let (b, b1) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        Zip::new(a.into_dyn(), b.into_dyn()).subscribe(
+            move |(a, b)| out.lock().unwrap().push_str(&format!("{}{},", a, b)),
+            move |_e| out1.lock().unwrap().push_str("ok")
+        );
+
+        let t = ::std::thread::spawn(move || {
+            for i in 0..3 { a1.next(i); }
+            a1.complete();
+        });
+        for i in 5..10 { b1.next(i); }
+        t.join().ok();
+
+        assert_eq!(out2.lock().unwrap().as_str(), "05,16,27,ok");
+    }
+}
@@ -0,0 +1,196 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::Cell;
+use std::cell::UnsafeCell;
+use std::time::Duration;
+
+pub struct AuditOp<SS, VBy, Src, Sch>
+{
+    due: Duration,
+    scheduler: Arc<Sch>,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>)>
+}
+
+pub trait ObsAuditOp<SS: YesNo, VBy: RefOrVal, Sch: Scheduler<SS>> : Sized
+{
+    //on completion a value still being audited is emitted right away, without waiting for the timer
+    fn audit(self, due: Duration, scheduler: Sch) -> AuditOp<SS, VBy, Self, Sch> { AuditOp{ due, scheduler: Arc::new(scheduler), src: self, PhantomData } }
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Src: Observable<'static, SS, VBy>, Sch: Scheduler<SS>+'static>
+ObsAuditOp<SS, VBy, Sch> for Src {}
+
+pub trait DynObsAuditOp<SS: YesNo, VBy: IntoOwned+'static, Sch: Scheduler<SS>+'static>
+{
+    fn audit(self, due: Duration, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<VBy::RAW>>;
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Sch: Scheduler<SS>+'static>
+DynObsAuditOp<SS, VBy, Sch>
+for DynObservable<'static, 'static, SS, VBy>
+{
+    fn audit(self, due: Duration, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<VBy::RAW>>
+    { AuditOp{ due, scheduler: Arc::new(scheduler), src: self.src, PhantomData }.into_dyn() }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct AuditState<SS:YesNo, N, EC, V, Sch>
+{
+    sub: Unsub<'static, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    due: Duration,
+    scheduler: Arc<Sch>,
+    latest: UnsafeCell<Option<V>>,
+    auditing: Cell<bool>,
+    timer: UnsafeCell<Unsub<'static, SS>>,
+}
+
+impl<SS:YesNo, N: ActNext<'static, SS, Val<V>>, EC: ActEc<'static, SS>, V: 'static, Sch: Scheduler<SS>+'static> AuditState<SS, N, EC, V, Sch>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+
+    fn flush(&self)
+    {
+        if let Some(v) = unsafe{ &mut *self.latest.get() }.take() {
+            if !self.next.stopped() { self.next.call(v); }
+        }
+    }
+}
+
+fn start_audit<SS:YesNo, N: ActNext<'static, SS, Val<V>>, EC: ActEc<'static, SS>, V: 'static, Sch: Scheduler<SS>+'static>
+(state: &Arc<AnySendSync<AuditState<SS, N, EC, V, Sch>>>)
+{
+    state.auditing.replace(true);
+
+    let timer = state.scheduler.schedule(Some(state.due), forward_act_once(SSWrap::new(state.clone()), |state, ()| {
+        state.sub.if_not_done(|| {
+            state.auditing.replace(false);
+            state.flush();
+        });
+        Unsub::done()
+    }));
+
+    if state.sub.is_done() { timer.unsub(); }
+    else { unsafe{ *state.timer.get() = timer; } }
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Src: Observable<'static, SS, VBy>, Sch: Scheduler<SS>+'static>
+Observable<'static, SS, Val<VBy::RAW>>
+for AuditOp<SS, VBy, Src, Sch>
+{
+    fn subscribe(&self, next: impl ActNext<'static, SS, Val<VBy::RAW>>, ec: impl ActEc<'static, SS>) -> Unsub<'static, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(AuditState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), due: self.due, scheduler: self.scheduler.clone(),
+            latest: UnsafeCell::new(None), auditing: Cell::new(false), timer: UnsafeCell::new(Unsub::done())
+        }) });
+
+        sub.add(Unsub::with(forward_act_once(SSWrap::new(state.clone()), |state, ()| {
+            unsafe{ &*state.timer.get() }.unsub();
+        })));
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| {
+                    unsafe{ *state.latest.get() = Some(v.into_owned()); }
+                    if !state.auditing.get() { start_audit(&*state); }
+                });
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| {
+                    unsafe{ &*state.timer.get() }.unsub();
+                    if e.is_none() { state.flush(); }
+                    state.end(e);
+                });
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'static, SS, Val<VBy::RAW>>>, ec: Box<ActEcBox<'static, SS>>) -> Unsub<'static, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn smoke()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1, out2) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.audit(Duration::from_millis(50), sch.clone()).subscribe(
+            move |v| out.lock().unwrap().push_str(&format!("{}", v)),
+            move |_e| out1.lock().unwrap().push_str("ok")
+        );
+
+        i.next(1);
+        i.next(2);
+        assert_eq!(out2.lock().unwrap().as_str(), "");
+
+        ::std::thread::sleep(Duration::from_millis(150));
+        assert_eq!(out2.lock().unwrap().as_str(), "2");
+
+        ::std::thread::sleep(Duration::from_millis(100));
+        assert_eq!(out2.lock().unwrap().as_str(), "2");
+
+        i.next(3);
+        i.complete();
+        assert_eq!(out2.lock().unwrap().as_str(), "23ok");
+    }
+
+    #[test]
+    fn unsub()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        let sub = o.into_dyn().audit(Duration::from_millis(30), sch.clone()).subscribe(
+            move |v| out.lock().unwrap().push_str(&format!("{}", v)), ()
+        );
+
+        i.next(1);
+        sub.unsub();
+
+        ::std::thread::sleep(Duration::from_millis(100));
+        assert_eq!(out1.lock().unwrap().as_str(), "");
+    }
+
+    #[test]
+    fn complete_flushes()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1, out2) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.audit(Duration::from_millis(200), sch.clone()).subscribe(
+            move |v| out.lock().unwrap().push_str(&format!("{}", v)),
+            move |_e| out1.lock().unwrap().push_str("ok")
+        );
+
+        i.next(1);
+        i.next(2);
+        i.complete();
+        assert_eq!(out2.lock().unwrap().as_str(), "2ok");
+
+        ::std::thread::sleep(Duration::from_millis(300));
+        assert_eq!(out2.lock().unwrap().as_str(), "2ok");
+    }
+}
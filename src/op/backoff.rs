@@ -0,0 +1,222 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::Cell;
+use std::cell::UnsafeCell;
+use std::time::Duration;
+
+pub struct BackoffOp<SS, Src, Sch>
+{
+    initial: Duration,
+    max_delay: Duration,
+    max_retries: usize,
+    scheduler: Arc<Sch>,
+    src: Src,
+    PhantomData: PhantomData<(SS)>
+}
+
+//delays of a plain `backoff` stop growing at one hour
+fn default_max_delay() -> Duration { Duration::from_secs(60 * 60) }
+
+//meant as a `retry_when` notifier: the n-th error emits `n` after `initial * 2^n`, the error past `max_retries` is forwarded
+pub trait ObsBackoffOp<SS: YesNo, Sch: Scheduler<SS>> : Sized
+{
+    fn backoff(self, initial: Duration, max_retries: usize, scheduler: Sch) -> BackoffOp<SS, Self, Sch>
+    { self.backoff_max(initial, default_max_delay(), max_retries, scheduler) }
+
+    fn backoff_max(self, initial: Duration, max_delay: Duration, max_retries: usize, scheduler: Sch) -> BackoffOp<SS, Self, Sch>
+    { BackoffOp{ initial, max_delay, max_retries, scheduler: Arc::new(scheduler), src: self, PhantomData } }
+}
+
+impl<SS:YesNo, Src: Observable<'static, SS, Ref<RxError>>, Sch: Scheduler<SS>+'static>
+ObsBackoffOp<SS, Sch> for Src {}
+
+pub trait DynObsBackoffOp<SS: YesNo, Sch: Scheduler<SS>+'static>
+{
+    fn backoff(self, initial: Duration, max_retries: usize, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<usize>>;
+    fn backoff_max(self, initial: Duration, max_delay: Duration, max_retries: usize, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<usize>>;
+}
+
+impl<SS:YesNo, Sch: Scheduler<SS>+'static>
+DynObsBackoffOp<SS, Sch>
+for DynObservable<'static, 'static, SS, Ref<RxError>>
+{
+    fn backoff(self, initial: Duration, max_retries: usize, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<usize>>
+    { self.backoff_max(initial, default_max_delay(), max_retries, scheduler) }
+
+    fn backoff_max(self, initial: Duration, max_delay: Duration, max_retries: usize, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<usize>>
+    { BackoffOp{ initial, max_delay, max_retries, scheduler: Arc::new(scheduler), src: self.src, PhantomData }.into_dyn() }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct BackoffState<SS:YesNo, N, EC, Sch>
+{
+    sub: Unsub<'static, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    initial: Duration,
+    max_delay: Duration,
+    max_retries: usize,
+    scheduler: Arc<Sch>,
+    count: Cell<usize>,
+    timer: UnsafeCell<Unsub<'static, SS>>,
+}
+
+impl<SS:YesNo, N: ActNext<'static, SS, Val<usize>>, EC: ActEc<'static, SS>, Sch: Scheduler<SS>+'static> BackoffState<SS, N, EC, Sch>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+
+    fn delay(&self, n: usize) -> Duration
+    {
+        1u32.checked_shl(n as u32).and_then(|factor| self.initial.checked_mul(factor)).map_or(self.max_delay, |d| d.min(self.max_delay))
+    }
+}
+
+fn schedule_retry<SS:YesNo, N: ActNext<'static, SS, Val<usize>>, EC: ActEc<'static, SS>, Sch: Scheduler<SS>+'static>
+(state: &Arc<AnySendSync<BackoffState<SS, N, EC, Sch>>>, n: usize)
+{
+    let timer = state.scheduler.schedule(Some(state.delay(n)), forward_act_once(SSWrap::new((state.clone(), n)), |caps, ()| {
+        let (state, n) = caps.into_inner();
+        state.sub.if_not_done(|| if !state.next.stopped() { state.next.call(n); });
+        Unsub::done()
+    }));
+
+    if state.sub.is_done() { timer.unsub(); }
+    else { unsafe{ ::std::mem::replace(&mut *state.timer.get(), timer) }.unsub(); }
+}
+
+impl<SS:YesNo, Src: Observable<'static, SS, Ref<RxError>>, Sch: Scheduler<SS>+'static>
+Observable<'static, SS, Val<usize>>
+for BackoffOp<SS, Src, Sch>
+{
+    fn subscribe(&self, next: impl ActNext<'static, SS, Val<usize>>, ec: impl ActEc<'static, SS>) -> Unsub<'static, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(BackoffState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), initial: self.initial, max_delay: self.max_delay, max_retries: self.max_retries,
+            scheduler: self.scheduler.clone(), count: Cell::new(0), timer: UnsafeCell::new(Unsub::done())
+        }) });
+
+        sub.add(Unsub::with(forward_act_once(SSWrap::new(state.clone()), |state, ()| {
+            unsafe{ &*state.timer.get() }.unsub();
+        })));
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, e: Ref<RxError>| {
+                state.sub.if_not_done(|| {
+                    let n = state.count.get();
+                    if n == state.max_retries {
+                        return state.end(Some(e.as_ref().clone()));
+                    }
+                    state.count.replace(n + 1);
+                    schedule_retry(&*state, n);
+                });
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| state.end(e));
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'static, SS, Val<usize>>>, ec: Box<ActEcBox<'static, SS>>) -> Unsub<'static, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    #[test]
+    fn smoke()
+    {
+        let (sch, sch1) = Arc::new(CurrentThreadScheduler::new()).clones();
+        let (out, out1, out2) = Rc::new(RefCell::new(String::new())).clones();
+        let (i, o) = Rc::new(Subject::<NO, RxError>::new()).clones();
+
+        //each emission triggers the next error, as a resubscribed flaky source would
+        sch.schedule(None, move || {
+            let i1 = i.clone();
+            o.backoff(Duration::from_millis(1), 2, sch1.clone()).subscribe(
+                move |n| {
+                    out.borrow_mut().push_str(&format!("{}", n));
+                    i1.next(RxError::simple(None, "error").set_handled());
+                },
+                move |e: Option<RxError>| out1.borrow_mut().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" })
+            );
+            i.next(RxError::simple(None, "error").set_handled());
+            Unsub::done()
+        });
+
+        assert_eq!(out2.borrow().as_str(), "01err");
+    }
+
+    #[test]
+    fn capped()
+    {
+        let (sch, sch1) = Arc::new(CurrentThreadScheduler::new()).clones();
+        let (out, out1, out2) = Rc::new(RefCell::new(Vec::new())).clones();
+        let (i, o) = Rc::new(Subject::<NO, RxError>::new()).clones();
+
+        //`2^40` overflows the delay: it has to stay at the cap instead
+        sch.schedule(None, move || {
+            let i1 = i.clone();
+            o.backoff_max(Duration::from_millis(1), Duration::from_millis(2), 40, sch1.clone()).subscribe(
+                move |n| {
+                    out.borrow_mut().push(n);
+                    i1.next(RxError::simple(None, "error").set_handled());
+                },
+                move |e: Option<RxError>| { e.map(|e| e.set_handled()); out1.borrow_mut().push(999); }
+            );
+            i.next(RxError::simple(None, "error").set_handled());
+            Unsub::done()
+        });
+
+        let mut expected: Vec<usize> = (0..40).collect();
+        expected.push(999);
+        assert_eq!(*out2.borrow(), expected);
+    }
+
+    #[test]
+    fn retry()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1, out2) = Arc::new(Mutex::new(String::new())).clones();
+        let (n, n1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let start = Instant::now();
+
+        let src = Arc::new(Subject::<YES, i32>::new());
+        src.error(RxError::simple(None, "error"));
+        src.retry_when(move |e: DynObservable<'static, 'static, YES, Ref<RxError>>| {
+            let n = n.clone();
+            e.backoff(Duration::from_millis(10), 3, sch.clone()).map(move |v: usize| { n.lock().unwrap().push((v, start.elapsed())); })
+        }).subscribe(
+            move |v: &i32| out.lock().unwrap().push_str(&format!("{}", v)),
+            move |e: Option<RxError>| out1.lock().unwrap().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" })
+        );
+
+        assert!(n1.lock().unwrap().is_empty());
+
+        ::std::thread::sleep(Duration::from_millis(300));
+        assert_eq!(out2.lock().unwrap().as_str(), "err");
+
+        //each retry waits twice as long as the one before
+        let n = n1.lock().unwrap();
+        assert_eq!(n.iter().map(|(v, _)| *v).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert!(n[0].1 >= Duration::from_millis(10));
+        assert!(n[1].1 - n[0].1 >= Duration::from_millis(20));
+        assert!(n[2].1 - n[1].1 >= Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,191 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+use std::time::Duration;
+
+pub struct BufferOp<'o, SS:YesNo, VBy, Src, SVBy: RefOrVal>
+{
+    src: Src,
+    sig: DynObservable<'o, 'o, SS, SVBy>,
+    PhantomData: PhantomData<AnySendSync<VBy>>
+}
+
+pub trait ObsBufferOp<'o, SS:YesNo, VBy: RefOrVal, SVBy: RefOrVal> : Sized
+{
+    fn buffer(self, sig: impl Observable<'o, SS, SVBy>+'o) -> BufferOp<'o, SS, VBy, Self, SVBy>
+    {
+        BufferOp{ src: self, sig: sig.into_dyn(), PhantomData }
+    }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, SVBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>>
+ObsBufferOp<'o, SS, VBy, SVBy>
+for Src {}
+
+pub trait DynObsBufferOp<'o, SS:YesNo, VBy: IntoOwned, SVBy: RefOrVal> : Sized
+{
+    fn buffer(self, sig: impl Observable<'o, SS, SVBy>+'o) -> DynObservable<'o, 'o, SS, Val<Vec<VBy::RAW>>>;
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, SVBy: RefOrVal+'o>
+DynObsBufferOp<'o, SS, VBy, SVBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn buffer(self, sig: impl Observable<'o, SS, SVBy>+'o) -> DynObservable<'o, 'o, SS, Val<Vec<VBy::RAW>>>
+    {
+        BufferOp{ src: self.src, sig: sig.into_dyn(), PhantomData }.into_dyn()
+    }
+}
+
+pub trait ObsBufferTimeOp<SS:YesNo, VBy: RefOrVal, Sch: SchedulerPeriodic<SS>> : Sized
+{
+    fn buffer_time(self, period: Duration, scheduler: Sch) -> BufferOp<'static, SS, VBy, Self, Val<usize>>;
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Src: Observable<'static, SS, VBy>, Sch: SchedulerPeriodic<SS>+'static>
+ObsBufferTimeOp<SS, VBy, Sch>
+for Src
+{
+    fn buffer_time(self, period: Duration, scheduler: Sch) -> BufferOp<'static, SS, VBy, Self, Val<usize>>
+    {
+        self.buffer(Timer::new(period, scheduler))
+    }
+}
+
+pub trait DynObsBufferTimeOp<SS:YesNo, VBy: IntoOwned, Sch: SchedulerPeriodic<SS>> : Sized
+{
+    fn buffer_time(self, period: Duration, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<Vec<VBy::RAW>>>;
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Sch: SchedulerPeriodic<SS>+'static>
+DynObsBufferTimeOp<SS, VBy, Sch>
+for DynObservable<'static, 'static, SS, VBy>
+{
+    fn buffer_time(self, period: Duration, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<Vec<VBy::RAW>>>
+    {
+        BufferOp{ src: self.src, sig: Timer::new(period, scheduler).into_dyn(), PhantomData }.into_dyn()
+    }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct BufferState<'o, SS:YesNo, N, EC, V>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    buf: UnsafeCell<Vec<V>>,
+}
+
+impl<'o, SS:YesNo, N: ActNext<'o, SS, Val<Vec<V>>>, EC: ActEc<'o, SS>, V> BufferState<'o, SS, N, EC, V>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+
+    fn flush(&self)
+    {
+        let buf = ::std::mem::replace(unsafe{ &mut *self.buf.get() }, Vec::new());
+        if !self.next.stopped() { self.next.call(buf); }
+    }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, SVBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<Vec<VBy::RAW>>>
+for BufferOp<'o, SS, VBy, Src, SVBy>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<Vec<VBy::RAW>>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(BufferState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), buf: UnsafeCell::new(Vec::new())
+        }) });
+
+        sub.add(self.sig.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, _v: SVBy| {
+                state.sub.if_not_done(|| state.flush());
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state.clone()), |state, e: Option<RxError>| {
+                if e.is_some() { state.sub.if_not_done(|| state.end(e)); }
+            })
+        ));
+
+        if sub.is_done() { return sub; }
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| unsafe{ &mut *state.buf.get() }.push(v.into_owned()));
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| {
+                    if e.is_none() && !unsafe{ &*state.buf.get() }.is_empty() { state.flush(); }
+                    state.end(e);
+                });
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<Vec<VBy::RAW>>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (s, sig) = Rc::new(Subject::<NO, ()>::new()).clones();
+
+        o.buffer(sig).subscribe(
+            |v: Vec<i32>| out.borrow_mut().push_str(&format!("{:?}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        i.next(1);
+        i.next(2);
+        s.next(());
+        s.next(());
+        i.next(3);
+        assert_eq!(out.borrow().as_str(), "[1, 2][]");
+
+        i.complete();
+        assert_eq!(out.borrow().as_str(), "[1, 2][][3]ok");
+    }
+
+    #[test]
+    fn buffer_time()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1, out2) = Arc::new(Mutex::new(Vec::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.into_dyn().buffer_time(Duration::from_millis(50), sch.clone()).subscribe(
+            move |v: Vec<i32>| if !v.is_empty() { out.lock().unwrap().push(v); },
+            move |_e| out1.lock().unwrap().push(vec![0])
+        );
+
+        i.next(1);
+        i.next(2);
+        ::std::thread::sleep(Duration::from_millis(120));
+        assert_eq!(*out2.lock().unwrap(), vec![vec![1, 2]]);
+
+        i.next(3);
+        i.complete();
+        assert_eq!(*out2.lock().unwrap(), vec![vec![1, 2], vec![3], vec![0]]);
+    }
+}
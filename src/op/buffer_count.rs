@@ -0,0 +1,224 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::Cell;
+use std::cell::UnsafeCell;
+use std::collections::VecDeque;
+
+pub struct BufferCountOp<SS, VBy: RefOrVal, Src>
+{
+    count: usize,
+    skip: usize,
+    //only `buffer_count_skip` can overlap buffers, so only it needs to clone values
+    clone: Option<fn(&VBy::RAW) -> VBy::RAW>,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>)>
+}
+
+pub trait ObsBufferCountOp<SS: YesNo, VBy: RefOrVal> : Sized
+{
+    //completes immediately when `count == 0`
+    fn buffer_count(self, count: usize) -> BufferCountOp<SS, VBy, Self>
+    { BufferCountOp{ count, skip: count, clone: None, src: self, PhantomData } }
+
+    //starts a new buffer every `skip` values, so buffers overlap when `skip < count`
+    fn buffer_count_skip(self, count: usize, skip: usize) -> BufferCountOp<SS, VBy, Self> where VBy::RAW: Clone
+    { BufferCountOp{ count, skip, clone: Some(Clone::clone), src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+ObsBufferCountOp<SS, VBy> for Src {}
+
+pub trait DynObsBufferCountOp<'o, SS: YesNo, VBy: IntoOwned+'o>
+{
+    fn buffer_count(self, count: usize) -> DynObservable<'o, 'o, SS, Val<Vec<VBy::RAW>>>;
+    fn buffer_count_skip(self, count: usize, skip: usize) -> DynObservable<'o, 'o, SS, Val<Vec<VBy::RAW>>> where VBy::RAW: Clone;
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o>
+DynObsBufferCountOp<'o, SS, VBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn buffer_count(self, count: usize) -> DynObservable<'o, 'o, SS, Val<Vec<VBy::RAW>>>
+    { BufferCountOp{ count, skip: count, clone: None, src: self.src, PhantomData }.into_dyn() }
+
+    fn buffer_count_skip(self, count: usize, skip: usize) -> DynObservable<'o, 'o, SS, Val<Vec<VBy::RAW>>> where VBy::RAW: Clone
+    { BufferCountOp{ count, skip, clone: Some(Clone::clone), src: self.src, PhantomData }.into_dyn() }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct BufferCountState<'o, SS:YesNo, N, EC, V>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    count: usize,
+    skip: usize,
+    clone: Option<fn(&V) -> V>,
+    index: Cell<usize>,
+    buffers: UnsafeCell<VecDeque<Vec<V>>>,
+}
+
+impl<'o, SS:YesNo, N: ActNext<'o, SS, Val<Vec<V>>>, EC: ActEc<'o, SS>, V> BufferCountState<'o, SS, N, EC, V>
+{
+    fn push(&self, v: V)
+    {
+        if self.index.get() % self.skip == 0 {
+            unsafe{ &mut *self.buffers.get() }.push_back(Vec::new());
+        }
+        self.index.replace(self.index.get().wrapping_add(1));
+
+        //only the newest buffer takes `v` itself, the older overlapping ones get clones
+        let buffers = unsafe{ &mut *self.buffers.get() };
+        let newest = buffers.len().wrapping_sub(1);
+        if let Some(clone) = self.clone {
+            for buf in buffers.iter_mut().take(newest) { buf.push(clone(&v)); }
+        }
+        if let Some(buf) = buffers.back_mut() { buf.push(v); }
+
+        let full = unsafe{ &*self.buffers.get() }.front().map_or(false, |buf| buf.len() == self.count);
+        if full {
+            let buf = unsafe{ &mut *self.buffers.get() }.pop_front().unwrap();
+            if !self.next.stopped() { self.next.call(buf); }
+        }
+    }
+
+    fn end(&self, e: Option<RxError>)
+    {
+        if e.is_none() {
+            while let Some(buf) = unsafe{ &mut *self.buffers.get() }.pop_front() {
+                if self.next.stopped() || self.sub.is_done() { break; }
+                if !buf.is_empty() { self.next.call(buf); }
+            }
+        }
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<Vec<VBy::RAW>>>
+for BufferCountOp<SS, VBy, Src>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<Vec<VBy::RAW>>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        if self.count == 0 || self.skip == 0 {
+            ec.call_once(None);
+            return Unsub::done();
+        }
+
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(BufferCountState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), count: self.count, skip: self.skip, clone: self.clone,
+            index: Cell::new(0), buffers: UnsafeCell::new(VecDeque::new())
+        }) });
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| state.push(v.into_owned()));
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| state.end(e));
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<Vec<VBy::RAW>>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.buffer_count(2).subscribe(
+            |v: Vec<i32>| out.borrow_mut().push_str(&format!("{:?}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        for v in 0..5 { i.next(v); }
+        assert_eq!(out.borrow().as_str(), "[0, 1][2, 3]");
+
+        i.complete();
+        assert_eq!(out.borrow().as_str(), "[0, 1][2, 3][4]ok");
+    }
+
+    #[test]
+    fn skip()
+    {
+        let out = RefCell::new(String::new());
+        iter_clone(0..5).buffer_count_skip(3, 1).subscribe(
+            |v: Vec<i32>| out.borrow_mut().push_str(&format!("{:?}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "[0, 1, 2][1, 2, 3][2, 3, 4][3, 4][4]ok");
+
+        let out = RefCell::new(String::new());
+        iter_clone(0..7).into_dyn().buffer_count_skip(2, 3).subscribe(
+            |v: Vec<i32>| out.borrow_mut().push_str(&format!("{:?}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "[0, 1][3, 4][6]ok");
+    }
+
+    #[test]
+    fn zero()
+    {
+        let out = RefCell::new(String::new());
+        iter_clone(0..5).buffer_count_skip(0, 1).subscribe(
+            |v: Vec<i32>| out.borrow_mut().push_str(&format!("{:?}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "ok");
+
+        let out = RefCell::new(String::new());
+        iter_clone(0..5).buffer_count_skip(2, 0).subscribe(
+            |v: Vec<i32>| out.borrow_mut().push_str(&format!("{:?}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "ok");
+    }
+
+    #[test]
+    fn not_clone()
+    {
+        struct NoClone(i32);
+        let out = RefCell::new(String::new());
+
+        iter_once((0..4).map(NoClone)).buffer_count(usize::max_value()).subscribe(
+            |v: Vec<NoClone>| out.borrow_mut().push_str(&format!("{}", v.iter().map(|v| v.0).sum::<i32>())),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        assert_eq!(out.borrow().as_str(), "6ok");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.buffer_count(3).subscribe(move |v: Vec<i32>| out.lock().unwrap().push(v.len()), ());
+
+        ::std::thread::spawn(move || {
+            for v in 0..10 { i.next(v); }
+            i.complete();
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), vec![3, 3, 3, 1]);
+    }
+}
@@ -0,0 +1,262 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+
+pub struct CatchErrorOp<SS, VBy, Src, F, O>
+{
+    f: Arc<F>,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>, AnySendSync<O>)>
+}
+
+pub struct OnErrorReturnOp<SS, VBy: RefOrVal, Src>
+{
+    value: VBy::RAW,
+    src: Src,
+    PhantomData: PhantomData<(SS)>
+}
+
+pub struct ResumeWith<'o, SS:YesNo, VBy: RefOrVal>(DynObservable<'o, 'o, SS, VBy>);
+
+unsafe impl<'o, SS:YesNo, VBy: RefOrVal>
+Act<SS, Val<RxError>, Arc<Observable<'o, SS, VBy>+'o>>
+for ResumeWith<'o, SS, VBy>
+{
+    #[inline(always)] fn call(&self, _: RxError) -> Arc<Observable<'o, SS, VBy>+'o> { self.0.as_impl() }
+}
+
+pub trait ObsCatchErrorOp<'o, SS: YesNo, VBy: RefOrVal+'o> : Sized
+{
+    //`f` gets the error already marked as handled
+    fn catch_error<O: Observable<'o, SS, VBy>+'o, F: Act<SS, Val<RxError>, O>+'o>(self, f: F) -> CatchErrorOp<SS, VBy, Self, F, O>
+    { CatchErrorOp{ f: Arc::new(f), src: self, PhantomData } }
+
+    fn on_error_resume_next(self, fallback: impl Observable<'o, SS, VBy>+'o) -> CatchErrorOp<SS, VBy, Self, ResumeWith<'o, SS, VBy>, Arc<Observable<'o, SS, VBy>+'o>>
+    { CatchErrorOp{ f: Arc::new(ResumeWith(fallback.into_dyn())), src: self, PhantomData } }
+
+    fn on_error_return(self, value: VBy::RAW) -> OnErrorReturnOp<SS, VBy, Self> where VBy: IntoOwned, VBy::RAW: Clone
+    { OnErrorReturnOp{ value, src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>>
+ObsCatchErrorOp<'o, SS, VBy> for Src {}
+
+pub trait DynObsCatchErrorOp<'o, SS: YesNo, VBy: RefOrVal+'o>
+{
+    fn catch_error<O: Observable<'o, SS, VBy>+'o, F: Act<SS, Val<RxError>, O>+'o>(self, f: F) -> Self;
+    fn on_error_resume_next(self, fallback: impl Observable<'o, SS, VBy>+'o) -> Self;
+    fn on_error_return(self, value: VBy::RAW) -> DynObservable<'o, 'o, SS, Val<VBy::RAW>> where VBy: IntoOwned, VBy::RAW: Clone+'o;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o>
+DynObsCatchErrorOp<'o, SS, VBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn catch_error<O: Observable<'o, SS, VBy>+'o, F: Act<SS, Val<RxError>, O>+'o>(self, f: F) -> Self
+    { CatchErrorOp{ f: Arc::new(f), src: self.src, PhantomData }.into_dyn() }
+
+    fn on_error_resume_next(self, fallback: impl Observable<'o, SS, VBy>+'o) -> Self
+    { CatchErrorOp::<SS, VBy, _, _, Arc<Observable<'o, SS, VBy>+'o>>{ f: Arc::new(ResumeWith(fallback.into_dyn())), src: self.src, PhantomData }.into_dyn() }
+
+    fn on_error_return(self, value: VBy::RAW) -> DynObservable<'o, 'o, SS, Val<VBy::RAW>> where VBy: IntoOwned, VBy::RAW: Clone+'o
+    { OnErrorReturnOp{ value, src: self.src, PhantomData }.into_dyn() }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct CatchErrorState<'o, SS:YesNo, N, EC>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+}
+
+impl<'o, SS:YesNo, N, EC: ActEc<'o, SS>> CatchErrorState<'o, SS, N, EC>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+}
+
+fn subscribe_replacement<'o, SS:YesNo, VBy: RefOrVal+'o, N: ActNext<'o, SS, VBy>, EC: ActEc<'o, SS>>
+(state: &Arc<AnySendSync<CatchErrorState<'o, SS, N, EC>>>, o: impl Observable<'o, SS, VBy>)
+{
+    state.sub.add(o.subscribe(
+        forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+            state.sub.if_not_done(|| state.next.call(v.into_v()));
+        }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+        forward_ec(SSWrap::new(state.clone()), |state, e: Option<RxError>| {
+            state.sub.if_not_done(|| state.end(e));
+        })
+    ));
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>, O: Observable<'o, SS, VBy>+'o, F: Act<SS, Val<RxError>, O>+'o>
+Observable<'o, SS, VBy>
+for CatchErrorOp<SS, VBy, Src, F, O>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, VBy>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let f = act_sendsync(self.f.clone());
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(CatchErrorState{ sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)) }) });
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| state.next.call(v.into_v()));
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec((SSWrap::new(state), f), |(state, f), e: Option<RxError>| {
+                state.sub.if_not_done(|| match e {
+                    Some(e) => subscribe_replacement(&*state, f.call(e.set_handled())),
+                    None => state.end(None)
+                });
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, VBy>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<VBy::RAW>>
+for OnErrorReturnOp<SS, VBy, Src>
+where VBy::RAW: Clone+'o
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<VBy::RAW>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(CatchErrorState{ sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)) }) });
+        let value = unsafe{ AnySendSync::new(UnsafeCell::new(Some(self.value.clone()))) };
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| state.next.call(v.into_owned()));
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec((SSWrap::new(state), SSWrap::new(value)), |(state, value), e: Option<RxError>| {
+                state.sub.if_not_done(|| {
+                    if let Some(e) = e {
+                        e.set_handled();
+                        if !state.next.stopped() {
+                            unsafe{ &mut *value.get() }.take().map(|v| state.next.call(v));
+                        }
+                    }
+                    state.end(None);
+                });
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<VBy::RAW>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.catch_error(|e: RxError| {
+            assert_eq!(e.sequence_error(), Some(SequenceError::NoElements));
+            Of::value(7)
+        }).subscribe(
+            |v: &i32| out.borrow_mut().push_str(&format!("{}", v)),
+            |e: Option<RxError>| out.borrow_mut().push_str(if e.is_some() { "err" } else { "ok" })
+        );
+
+        i.next(1);
+        i.error(RxError::sequence(SequenceError::NoElements));
+        assert_eq!(out.borrow().as_str(), "17ok");
+    }
+
+    #[test]
+    fn complete_passes_through()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(1..3).into_dyn().catch_error(|_e: RxError| iter_clone(7..9)).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "12ok");
+    }
+
+    #[test]
+    fn replacement_error()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.catch_error(|_e: RxError| Of::<NO, i32>::empty().first()).subscribe(
+            |v: &i32| out.borrow_mut().push_str(&format!("{}", v)),
+            |e: Option<RxError>| out.borrow_mut().push_str(&format!("{:?}", e.unwrap().set_handled().sequence_error()))
+        );
+
+        i.error(RxError::simple(None, "error"));
+        assert_eq!(out.borrow().as_str(), "Some(NoElements)");
+    }
+
+    #[test]
+    fn resume_next()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.on_error_resume_next(Of::value(9)).subscribe(
+            |v: &i32| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        i.next(1);
+        i.error(RxError::simple(None, "error"));
+        assert_eq!(out.borrow().as_str(), "19ok");
+    }
+
+    #[test]
+    fn error_return()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, String>::new()).clones();
+
+        o.into_dyn().on_error_return("z".to_owned()).subscribe(
+            |v: String| out.borrow_mut().push_str(&v),
+            |e: Option<RxError>| out.borrow_mut().push_str(if e.is_some() { "err" } else { "ok" })
+        );
+
+        i.next("a".to_owned());
+        i.error(RxError::simple(None, "error"));
+        assert_eq!(out.borrow().as_str(), "azok");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.on_error_return(-1).subscribe(move |v| out.lock().unwrap().push(v), ());
+
+        ::std::thread::spawn(move || {
+            for v in 0..3 { i.next(v); }
+            i.error(RxError::simple(None, "error"));
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), vec![0, 1, 2, -1]);
+    }
+}
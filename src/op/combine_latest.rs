@@ -0,0 +1,398 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+
+pub struct CombineLatestOp<'o, SS:YesNo, VBy, Src, ByB: RefOrVal>
+{
+    src: Src,
+    other: DynObservable<'o, 'o, SS, ByB>,
+    PhantomData: PhantomData<AnySendSync<VBy>>
+}
+
+pub trait ObsCombineLatestOp<'o, SS:YesNo, VBy: RefOrVal, ByB: RefOrVal> : Sized
+{
+    fn combine_latest(self, other: impl Observable<'o, SS, ByB>+'o) -> CombineLatestOp<'o, SS, VBy, Self, ByB>
+    {
+        CombineLatestOp{ src: self, other: other.into_dyn(), PhantomData }
+    }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned, ByB: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+ObsCombineLatestOp<'o, SS, VBy, ByB>
+for Src {}
+
+pub trait DynObsCombineLatestOp<'o, SS:YesNo, VBy: IntoOwned, ByB: IntoOwned> : Sized
+{
+    fn combine_latest(self, other: impl Observable<'o, SS, ByB>+'o) -> DynObservable<'o, 'o, SS, Val<(VBy::RAW, ByB::RAW)>>;
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, ByB: IntoOwned+'o>
+DynObsCombineLatestOp<'o, SS, VBy, ByB>
+for DynObservable<'o, 'o, SS, VBy>
+where VBy::RAW: Clone, ByB::RAW: Clone
+{
+    fn combine_latest(self, other: impl Observable<'o, SS, ByB>+'o) -> DynObservable<'o, 'o, SS, Val<(VBy::RAW, ByB::RAW)>>
+    {
+        CombineLatestOp{ src: self.src, other: other.into_dyn(), PhantomData }.into_dyn()
+    }
+}
+
+//`ec`, `a` & `b` are protected by `lock`
+struct CombineLatestState<'o, SS:YesNo, N, EC, A, B>
+{
+    lock: ReSpinLock<SS>,
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    a: UnsafeCell<(Option<A>, bool)>,
+    b: UnsafeCell<(Option<B>, bool)>,
+}
+
+impl<'o, SS:YesNo, N: ActNext<'o, SS, Val<(A, B)>>, EC: ActEc<'o, SS>, A: Clone, B: Clone> CombineLatestState<'o, SS, N, EC, A, B>
+{
+    fn emit(&self)
+    {
+        let (a, b) = unsafe{ (&*self.a.get(), &*self.b.get()) };
+        if let (Some(a), Some(b)) = (&a.0, &b.0) {
+            let v = (a.clone(), b.clone());
+            if !self.sub.is_done() { self.next.call(v); }
+        }
+    }
+
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+
+    fn end_if_done(&self)
+    {
+        let (a, b) = unsafe{ (&*self.a.get(), &*self.b.get()) };
+        if (a.1 && b.1) || (a.1 && a.0.is_none()) || (b.1 && b.0.is_none()) {
+            self.end(None);
+        }
+    }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, ByB: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<(VBy::RAW, ByB::RAW)>>
+for CombineLatestOp<'o, SS, VBy, Src, ByB>
+where VBy::RAW: Clone, ByB::RAW: Clone
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<(VBy::RAW, ByB::RAW)>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(CombineLatestState{
+            lock: ReSpinLock::new(), sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)),
+            a: UnsafeCell::new((None, false)), b: UnsafeCell::new((None, false))
+        }) });
+
+        sub.add(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.lock.enter();
+                unsafe{ &mut *state.a.get() }.0 = Some(v.into_owned());
+                state.emit();
+                state.lock.exit();
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state.clone()), |state, e: Option<RxError>| {
+                state.lock.enter();
+                unsafe{ &mut *state.a.get() }.1 = true;
+                if e.is_some() { state.end(e); } else { state.end_if_done(); }
+                state.lock.exit();
+            })
+        ));
+
+        if sub.is_done() { return sub; }
+
+        sub.clone().added(self.other.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: ByB| {
+                state.lock.enter();
+                unsafe{ &mut *state.b.get() }.0 = Some(v.into_owned());
+                state.emit();
+                state.lock.exit();
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.lock.enter();
+                unsafe{ &mut *state.b.get() }.1 = true;
+                if e.is_some() { state.end(e); } else { state.end_if_done(); }
+                state.lock.exit();
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<(VBy::RAW, ByB::RAW)>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+//combines any number of sources of the same type, emitting one `Vec` with the latest value of each
+pub struct CombineLatestAllOp<'o, SS:YesNo, By: RefOrVal>
+{
+    obs: Vec<DynObservable<'o, 'o, SS, By>>
+}
+
+pub trait ObsCombineLatestAllOp<'o, SS:YesNo, By: RefOrVal> : Sized
+{
+    fn combine_latest_all(self, others: impl Into<Vec<DynObservable<'o, 'o, SS, By>>>) -> CombineLatestAllOp<'o, SS, By>;
+}
+
+impl<'o, SS:YesNo, By: IntoOwned+'o, Src: Observable<'o, SS, By>+'o>
+ObsCombineLatestAllOp<'o, SS, By>
+for Src
+{
+    fn combine_latest_all(self, others: impl Into<Vec<DynObservable<'o, 'o, SS, By>>>) -> CombineLatestAllOp<'o, SS, By>
+    {
+        let mut obs = others.into();
+        obs.insert(0, self.into_dyn());
+        CombineLatestAllOp{ obs }
+    }
+}
+
+pub trait DynObsCombineLatestAllOp<'o, SS:YesNo, By: IntoOwned> : Sized
+{
+    fn combine_latest_all(self, others: impl Into<Vec<DynObservable<'o, 'o, SS, By>>>) -> DynObservable<'o, 'o, SS, Val<Vec<By::RAW>>>;
+}
+
+impl<'o, SS:YesNo, By: IntoOwned+'o>
+DynObsCombineLatestAllOp<'o, SS, By>
+for DynObservable<'o, 'o, SS, By>
+where By::RAW: Clone
+{
+    fn combine_latest_all(self, others: impl Into<Vec<DynObservable<'o, 'o, SS, By>>>) -> DynObservable<'o, 'o, SS, Val<Vec<By::RAW>>>
+    {
+        let mut obs = others.into();
+        obs.insert(0, self);
+        CombineLatestAllOp{ obs }.into_dyn()
+    }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct CombineLatestAllState<'o, SS:YesNo, N, EC, V>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    latest: UnsafeCell<Vec<(Option<V>, bool)>>,
+}
+
+impl<'o, SS:YesNo, N: ActNext<'o, SS, Val<Vec<V>>>, EC: ActEc<'o, SS>, V: Clone> CombineLatestAllState<'o, SS, N, EC, V>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+
+    fn push(&self, index: usize, v: V)
+    {
+        let latest = unsafe{ &mut *self.latest.get() };
+        latest[index].0 = Some(v);
+
+        if latest.iter().all(|(v, _)| v.is_some()) {
+            let vs = latest.iter().map(|(v, _)| v.clone().unwrap()).collect();
+            self.next.call(vs);
+        }
+    }
+
+    fn complete(&self, index: usize)
+    {
+        let latest = unsafe{ &mut *self.latest.get() };
+        latest[index].1 = true;
+
+        //a source that completes without a value means nothing can be emitted anymore
+        if latest[index].0.is_none() || latest.iter().all(|(_, done)| *done) {
+            self.end(None);
+        }
+    }
+}
+
+impl<'o, SS:YesNo, By: IntoOwned+'o>
+Observable<'o, SS, Val<Vec<By::RAW>>>
+for CombineLatestAllOp<'o, SS, By>
+where By::RAW: Clone
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<Vec<By::RAW>>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(CombineLatestAllState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)),
+            latest: UnsafeCell::new(self.obs.iter().map(|_| (None, false)).collect())
+        }) });
+
+        for (index, o) in self.obs.iter().enumerate() {
+            if sub.is_done() { break; }
+
+            sub.add(o.subscribe(
+                forward_next((), SSWrap::new((state.clone(), index)), |(), caps, v: By| {
+                    let (state, index) = &**caps;
+                    state.sub.if_not_done(|| state.push(*index, v.into_owned()));
+                }, |(), caps| caps.0.sub.is_done() || caps.0.next.stopped()),
+
+                forward_ec(SSWrap::new((state.clone(), index)), |caps, e: Option<RxError>| {
+                    let (state, index) = caps.into_inner();
+                    state.sub.if_not_done(|| if e.is_some() { state.end(e) } else { state.complete(index) });
+                })
+            ));
+        }
+
+        sub
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<Vec<By::RAW>>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+        let (a, a1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (b, b1) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        a.combine_latest(b).subscribe(
+            |(a, b)| out.borrow_mut().push_str(&format!("{}{},", a, b)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        a1.next(1);
+        a1.next(2);
+        b1.next(1);
+        a1.next(3);
+        a1.complete();
+        b1.next(2);
+        assert_eq!(out.borrow().as_str(), "21,31,32,");
+
+        b1.complete();
+        assert_eq!(out.borrow().as_str(), "21,31,32,ok");
+    }
+
+    #[test]
+    fn behavior_subjects()
+    {
+        let out = RefCell::new(String::new());
+        let (a, a1) = Rc::new(BehaviorSubject::<NO, i32>::new(1)).clones();
+        let (b, b1) = Rc::new(BehaviorSubject::<NO, String>::new("x".to_owned())).clones();
+
+        a.into_dyn().combine_latest(b).subscribe(
+            |(a, b)| out.borrow_mut().push_str(&format!("{}{},", a, b)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "1x,");
+
+        b1.next("y".to_owned());
+        a1.next(2);
+        assert_eq!(out.borrow().as_str(), "1x,1y,2y,");
+    }
+
+    #[test]
+    fn empty()
+    {
+        let out = RefCell::new(String::new());
+        let (b, b1) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        Of::<NO, i32>::empty().combine_latest(b).subscribe(
+            |(a, b)| out.borrow_mut().push_str(&format!("{}{},", a, b)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "ok");
+
+        b1.next(1);
+        assert_eq!(out.borrow().as_str(), "ok");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(0)).clones();
+        let (a, a1) = Arc::new(Subject::<YES, i32>::new()).clones();
+        let (b, b1) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        a.combine_latest(b).subscribe(move |(a, b)| *out.lock().unwrap() = a * b, ());
+
+        b1.next(2);
+        ::std::thread::spawn(move || {
+            for i in 0..10 { a1.next(i); }
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), 18);
+    }
+
+    #[test]
+    fn all()
+    {
+        let out = RefCell::new(String::new());
+        let (a, a1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (b, b1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (c, c1) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        a.combine_latest_all(vec![b.into_dyn(), c.into_dyn()]).subscribe(
+            |vs: Vec<i32>| out.borrow_mut().push_str(&format!("{:?},", vs)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        a1.next(1);
+        b1.next(2);
+        a1.next(3);
+        c1.next(4);
+        b1.next(5);
+        a1.complete();
+        c1.next(6);
+        b1.complete();
+        assert_eq!(out.borrow().as_str(), "[3, 2, 4],[3, 5, 4],[3, 5, 6],");
+
+        c1.complete();
+        assert_eq!(out.borrow().as_str(), "[3, 2, 4],[3, 5, 4],[3, 5, 6],ok");
+    }
+
+    #[test]
+    fn all_empty()
+    {
+        let out = RefCell::new(String::new());
+        let (a, a1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (b, b1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (c, c1) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        a.combine_latest_all(vec![b.into_dyn(), c.into_dyn()]).subscribe(
+            |vs: Vec<i32>| out.borrow_mut().push_str(&format!("{:?},", vs)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        a1.next(1);
+        b1.next(2);
+        c1.complete();
+        assert_eq!(out.borrow().as_str(), "ok");
+
+        a1.next(3);
+        assert_eq!(out.borrow().as_str(), "ok");
+    }
+
+    #[test]
+    fn all_thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(0)).clones();
+        let (a, a1) = Arc::new(Subject::<YES, i32>::new()).clones();
+        let (b, b1) = Arc::new(Subject::<YES, i32>::new()).clones();
+        let (c, c1) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        a.combine_latest_all(vec![b.into_dyn(), c.into_dyn()]).subscribe(move |vs: Vec<i32>| *out.lock().unwrap() = vs.iter().product(), ());
+
+        b1.next(2);
+        c1.next(3);
+        ::std::thread::spawn(move || {
+            for i in 0..10 { a1.next(i); }
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), 54);
+    }
+}
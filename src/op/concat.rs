@@ -0,0 +1,57 @@
+use crate::*;
+
+pub trait ObsConcatOp<'o, SS:YesNo, By: RefOrVal> : Sized
+{
+    fn concat(self, other: impl Observable<'o, SS, By>+'o) -> Concat<'o, 'o, SS, By>;
+}
+
+impl<'o, SS:YesNo, By: RefOrVal+'o, Src: Observable<'o, SS, By>+'o>
+ObsConcatOp<'o, SS, By>
+for Src
+{
+    fn concat(self, other: impl Observable<'o, SS, By>+'o) -> Concat<'o, 'o, SS, By>
+    {
+        Concat::new(vec![self.into_dyn(), other.into_dyn()])
+    }
+}
+
+pub trait DynObsConcatOp<'o, SS:YesNo, By: RefOrVal> : Sized
+{
+    fn concat(self, other: impl Observable<'o, SS, By>+'o) -> DynObservable<'o, 'o, SS, By>;
+}
+
+impl<'o, SS:YesNo, By: RefOrVal+'o>
+DynObsConcatOp<'o, SS, By>
+for DynObservable<'o, 'o, SS, By>
+{
+    fn concat(self, other: impl Observable<'o, SS, By>+'o) -> DynObservable<'o, 'o, SS, By>
+    {
+        Concat::new(vec![self, other.into_dyn()]).into_dyn()
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use std::rc::Rc;
+    use std::cell::RefCell;
+    use crate::util::clones::Clones;
+
+    #[test]
+    fn smoke()
+    {
+        let (n, n1, n2) = Rc::new(RefCell::new(String::new())).clones();
+
+        let a = Of::value(123);
+        let b = Of::<NO, i32>::value(456);
+
+        a.concat(b).into_dyn().concat(Of::value(789)).subscribe(|v:&_|{
+            n1.borrow_mut().push_str(&format!("{}", v));
+        }, |_| {
+            n2.borrow_mut().push_str("ok");
+        });
+
+        assert_eq!("123456789ok", n.borrow().as_str());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::*;
+
+pub trait ObsConcatMapOp<'o, SS:YesNo, VBy: RefOrVal, O, F: Act<SS, VBy, O>+'o> : Sized
+{
+    fn concat_map(self, f: F) -> FlatMapOp<SS, VBy, Self, F, O>;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, O, Src: Observable<'o, SS, VBy>+'o, F: Act<SS, VBy, O>+'o>
+ObsConcatMapOp<'o, SS, VBy, O, F>
+for Src
+{
+    fn concat_map(self, f: F) -> FlatMapOp<SS, VBy, Self, F, O>
+    {
+        self.flat_map_max(1, f)
+    }
+}
+
+pub trait DynObsConcatMapOp<'o, SS:YesNo, VBy: RefOrVal+'o, By: RefOrVal+'o, O: Observable<'o, SS, By>+'o, F: Act<SS, VBy, O>+'o> : Sized
+{
+    fn concat_map(self, f: F) -> DynObservable<'o, 'o, SS, By>;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, By: RefOrVal+'o, O: Observable<'o, SS, By>+'o, F: Act<SS, VBy, O>+'o>
+DynObsConcatMapOp<'o, SS, VBy, By, O, F>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn concat_map(self, f: F) -> DynObservable<'o, 'o, SS, By>
+    {
+        self.flat_map_max(1, f)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (a, a1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (b, b1) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.concat_map(move |v:&i32| if *v == 0 { a.clone() } else { b.clone() }).subscribe(
+            |v:&_| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        i.next(0);
+        i.next(1);
+        i.complete();
+
+        b1.next(1);
+        a1.next(2);
+        a1.complete();
+        b1.next(3);
+        assert_eq!(out.borrow().as_str(), "23");
+
+        b1.complete();
+        assert_eq!(out.borrow().as_str(), "23ok");
+    }
+
+    #[test]
+    fn sync_inner()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(1..4).into_dyn().concat_map(|v| iter_clone(0..v)).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        assert_eq!(out.borrow().as_str(), "001012ok");
+    }
+}
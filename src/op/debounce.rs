@@ -0,0 +1,189 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::Cell;
+use std::cell::UnsafeCell;
+use std::time::Duration;
+
+pub struct DebounceOp<SS, VBy, Src, Sch>
+{
+    due: Duration,
+    scheduler: Arc<Sch>,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>)>
+}
+
+pub trait ObsDebounceOp<SS: YesNo, VBy: RefOrVal, Sch: Scheduler<SS>> : Sized
+{
+    fn debounce(self, due: Duration, scheduler: Sch) -> DebounceOp<SS, VBy, Self, Sch> { DebounceOp{ due, scheduler: Arc::new(scheduler), src: self, PhantomData } }
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Src: Observable<'static, SS, VBy>, Sch: Scheduler<SS>+'static>
+ObsDebounceOp<SS, VBy, Sch> for Src {}
+
+pub trait DynObsDebounceOp<SS: YesNo, VBy: IntoOwned+'static, Sch: Scheduler<SS>+'static>
+{
+    fn debounce(self, due: Duration, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<VBy::RAW>>;
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Sch: Scheduler<SS>+'static>
+DynObsDebounceOp<SS, VBy, Sch>
+for DynObservable<'static, 'static, SS, VBy>
+{
+    fn debounce(self, due: Duration, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<VBy::RAW>>
+    { DebounceOp{ due, scheduler: Arc::new(scheduler), src: self.src, PhantomData }.into_dyn() }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct DebounceState<SS:YesNo, N, EC, V, Sch>
+{
+    sub: Unsub<'static, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    due: Duration,
+    scheduler: Arc<Sch>,
+    pending: UnsafeCell<Option<V>>,
+    //bumped on every value, so a timer that already fired but lost the race for the lock won't emit a newer value early
+    id: Cell<usize>,
+    timer: UnsafeCell<Unsub<'static, SS>>,
+}
+
+impl<SS:YesNo, N: ActNext<'static, SS, Val<V>>, EC: ActEc<'static, SS>, V: 'static, Sch: Scheduler<SS>+'static> DebounceState<SS, N, EC, V, Sch>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+
+    fn flush(&self)
+    {
+        if let Some(v) = unsafe{ &mut *self.pending.get() }.take() {
+            if !self.next.stopped() { self.next.call(v); }
+        }
+    }
+}
+
+fn schedule_emit<SS:YesNo, N: ActNext<'static, SS, Val<V>>, EC: ActEc<'static, SS>, V: 'static, Sch: Scheduler<SS>+'static>
+(state: &Arc<AnySendSync<DebounceState<SS, N, EC, V, Sch>>>)
+{
+    unsafe{ ::std::mem::replace(&mut *state.timer.get(), Unsub::done()) }.unsub();
+
+    let timer = state.scheduler.schedule(Some(state.due), forward_act_once(SSWrap::new((state.clone(), state.id.get())), |caps, ()| {
+        let (state, id) = caps.into_inner();
+        state.sub.if_not_done(|| if state.id.get() == id { state.flush(); });
+        Unsub::done()
+    }));
+
+    if state.sub.is_done() { timer.unsub(); }
+    else { unsafe{ *state.timer.get() = timer; } }
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Src: Observable<'static, SS, VBy>, Sch: Scheduler<SS>+'static>
+Observable<'static, SS, Val<VBy::RAW>>
+for DebounceOp<SS, VBy, Src, Sch>
+{
+    fn subscribe(&self, next: impl ActNext<'static, SS, Val<VBy::RAW>>, ec: impl ActEc<'static, SS>) -> Unsub<'static, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(DebounceState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), due: self.due, scheduler: self.scheduler.clone(),
+            pending: UnsafeCell::new(None), id: Cell::new(0), timer: UnsafeCell::new(Unsub::done())
+        }) });
+
+        sub.add(Unsub::with(forward_act_once(SSWrap::new(state.clone()), |state, ()| {
+            unsafe{ &*state.timer.get() }.unsub();
+        })));
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| {
+                    unsafe{ *state.pending.get() = Some(v.into_owned()); }
+                    state.id.replace(state.id.get().wrapping_add(1));
+                    schedule_emit(&*state);
+                });
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| {
+                    unsafe{ &*state.timer.get() }.unsub();
+                    if e.is_none() { state.flush(); }
+                    state.end(e);
+                });
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'static, SS, Val<VBy::RAW>>>, ec: Box<ActEcBox<'static, SS>>) -> Unsub<'static, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn smoke()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1, out2) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.debounce(Duration::from_millis(50), sch.clone()).subscribe(
+            move |v| out.lock().unwrap().push_str(&format!("{}", v)),
+            move |_e| out1.lock().unwrap().push_str("ok")
+        );
+
+        i.next(1);
+        i.next(2);
+        assert_eq!(out2.lock().unwrap().as_str(), "");
+
+        ::std::thread::sleep(Duration::from_millis(200));
+        assert_eq!(out2.lock().unwrap().as_str(), "2");
+
+        i.next(3);
+        i.complete();
+        assert_eq!(out2.lock().unwrap().as_str(), "23ok");
+    }
+
+    #[test]
+    fn unsub()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        let sub = o.into_dyn().debounce(Duration::from_millis(20), sch.clone()).subscribe(
+            move |v| out.lock().unwrap().push_str(&format!("{}", v)), ()
+        );
+
+        i.next(1);
+        sub.unsub();
+
+        ::std::thread::sleep(Duration::from_millis(100));
+        assert_eq!(out1.lock().unwrap().as_str(), "");
+    }
+
+    #[test]
+    fn current_thread()
+    {
+        let (sch, sch1) = Arc::new(CurrentThreadScheduler::new()).clones();
+        let (out, out1, out2) = Rc::new(RefCell::new(String::new())).clones();
+
+        sch.schedule(None, move || {
+            iter_clone(0..3).debounce(Duration::from_millis(1), sch1).subscribe(
+                move |v| out.borrow_mut().push_str(&format!("{}", v)),
+                move |_e| out1.borrow_mut().push_str("ok")
+            )
+        });
+
+        assert_eq!(out2.borrow().as_str(), "2ok");
+    }
+}
@@ -0,0 +1,314 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::Cell;
+use std::cell::UnsafeCell;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+pub struct DelayOp<SS, VBy, Src, Sch, F>
+{
+    due: Duration,
+    f: Arc<F>,
+    delay_error: bool,
+    scheduler: Arc<Sch>,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>)>
+}
+
+pub struct DelayFixed(Duration);
+
+unsafe impl<SS:YesNo, V>
+Act<SS, Ref<V>, Duration>
+for DelayFixed
+{
+    #[inline(always)] fn call(&self, _: *const V) -> Duration { self.0 }
+}
+
+impl<SS, VBy, Src, Sch, F> DelayOp<SS, VBy, Src, Sch, F>
+{
+    //errors are forwarded immediately by default, dropping all pending values
+    pub fn delay_error(self, delay_error: bool) -> Self { DelayOp{ delay_error, ..self } }
+}
+
+pub trait ObsDelayOp<SS: YesNo, VBy: IntoOwned, Sch: Scheduler<SS>> : Sized
+{
+    fn delay(self, due: Duration, scheduler: Sch) -> DelayOp<SS, VBy, Self, Sch, DelayFixed>
+    { DelayOp{ due, f: Arc::new(DelayFixed(due)), delay_error: false, scheduler: Arc::new(scheduler), src: self, PhantomData } }
+
+    fn delay_by<F: Act<SS, Ref<VBy::RAW>, Duration>>(self, scheduler: Sch, f: F) -> DelayOp<SS, VBy, Self, Sch, F>
+    { DelayOp{ due: Duration::new(0, 0), f: Arc::new(f), delay_error: false, scheduler: Arc::new(scheduler), src: self, PhantomData } }
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Src: Observable<'static, SS, VBy>, Sch: Scheduler<SS>+'static>
+ObsDelayOp<SS, VBy, Sch> for Src {}
+
+pub trait DynObsDelayOp<SS: YesNo, VBy: IntoOwned+'static, Sch: Scheduler<SS>+'static>
+{
+    fn delay(self, due: Duration, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<VBy::RAW>>;
+    fn delay_by<F: Act<SS, Ref<VBy::RAW>, Duration>+'static>(self, scheduler: Sch, f: F) -> DynObservable<'static, 'static, SS, Val<VBy::RAW>>;
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Sch: Scheduler<SS>+'static>
+DynObsDelayOp<SS, VBy, Sch>
+for DynObservable<'static, 'static, SS, VBy>
+{
+    fn delay(self, due: Duration, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<VBy::RAW>>
+    { DelayOp{ due, f: Arc::new(DelayFixed(due)), delay_error: false, scheduler: Arc::new(scheduler), src: self.src, PhantomData }.into_dyn() }
+
+    fn delay_by<F: Act<SS, Ref<VBy::RAW>, Duration>+'static>(self, scheduler: Sch, f: F) -> DynObservable<'static, 'static, SS, Val<VBy::RAW>>
+    { DelayOp{ due: Duration::new(0, 0), f: Arc::new(f), delay_error: false, scheduler: Arc::new(scheduler), src: self.src, PhantomData }.into_dyn() }
+}
+
+enum DelayItem<V>
+{
+    Next(V),
+    End(Option<RxError>),
+}
+
+struct DelayEntry<SS:YesNo, V>
+{
+    id: usize,
+    ready: bool,
+    item: DelayItem<V>,
+    timer: Unsub<'static, SS>,
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct DelayState<SS:YesNo, N, EC, V, Sch, F>
+{
+    sub: Unsub<'static, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    due: Duration,
+    f: Arc<F>,
+    scheduler: Arc<Sch>,
+    id: Cell<usize>,
+    queue: UnsafeCell<VecDeque<DelayEntry<SS, V>>>,
+}
+
+impl<SS:YesNo, N: ActNext<'static, SS, Val<V>>, EC: ActEc<'static, SS>, V: 'static, Sch: Scheduler<SS>+'static, F> DelayState<SS, N, EC, V, Sch, F>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+
+    fn clear(&self)
+    {
+        for entry in unsafe{ &mut *self.queue.get() }.drain(..) {
+            entry.timer.unsub();
+            if let DelayItem::End(Some(e)) = entry.item { e.set_handled(); }
+        }
+    }
+
+    //emits every ready item at the front, so a shorter delay never overtakes an earlier notification
+    fn drain(&self)
+    {
+        loop {
+            let queue = unsafe{ &mut *self.queue.get() };
+            if !queue.front().map_or(false, |entry| entry.ready) { return; }
+
+            match queue.pop_front().unwrap().item {
+                DelayItem::Next(v) => if !self.next.stopped() { self.next.call(v); },
+                DelayItem::End(e) => return self.end(e),
+            }
+            if self.sub.is_done() { return; }
+        }
+    }
+}
+
+fn schedule_item<SS:YesNo, N: ActNext<'static, SS, Val<V>>, EC: ActEc<'static, SS>, V: 'static, Sch: Scheduler<SS>+'static, F: 'static>
+(state: &Arc<AnySendSync<DelayState<SS, N, EC, V, Sch, F>>>, due: Duration, item: DelayItem<V>)
+{
+    let id = state.id.get();
+    state.id.replace(id.wrapping_add(1));
+    unsafe{ &mut *state.queue.get() }.push_back(DelayEntry{ id, ready: false, item, timer: Unsub::done() });
+
+    let timer = state.scheduler.schedule(Some(due), forward_act_once(SSWrap::new((state.clone(), id)), |caps, ()| {
+        let (state, id) = caps.into_inner();
+        state.sub.if_not_done(|| {
+            if let Some(entry) = unsafe{ &mut *state.queue.get() }.iter_mut().find(|entry| entry.id == id) {
+                entry.ready = true;
+            }
+            state.drain();
+        });
+        Unsub::done()
+    }));
+
+    //a synchronous scheduler may already have run the item
+    match unsafe{ &mut *state.queue.get() }.iter_mut().find(|entry| entry.id == id) {
+        Some(entry) if !state.sub.is_done() => entry.timer = timer,
+        _ => timer.unsub()
+    }
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Src: Observable<'static, SS, VBy>, Sch: Scheduler<SS>+'static, F: Act<SS, Ref<VBy::RAW>, Duration>+'static>
+Observable<'static, SS, Val<VBy::RAW>>
+for DelayOp<SS, VBy, Src, Sch, F>
+{
+    fn subscribe(&self, next: impl ActNext<'static, SS, Val<VBy::RAW>>, ec: impl ActEc<'static, SS>) -> Unsub<'static, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let delay_error = self.delay_error;
+        let state = Arc::new(unsafe{ AnySendSync::new(DelayState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), due: self.due, f: self.f.clone(), scheduler: self.scheduler.clone(),
+            id: Cell::new(0), queue: UnsafeCell::new(VecDeque::new())
+        }) });
+
+        sub.add(Unsub::with(forward_act_once(SSWrap::new(state.clone()), |state, ()| state.clear())));
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| {
+                    let v = v.into_owned();
+                    let due = state.f.call(&v);
+                    schedule_item(&*state, due, DelayItem::Next(v));
+                });
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec((SSWrap::new(state), SSWrap::new(delay_error)), |(state, delay_error), e: Option<RxError>| {
+                state.sub.if_not_done(|| {
+                    if e.is_some() && !*delay_error {
+                        state.clear();
+                        return state.end(e);
+                    }
+                    let due = state.due;
+                    schedule_item(&*state, due, DelayItem::End(e));
+                });
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'static, SS, Val<VBy::RAW>>>, ec: Box<ActEcBox<'static, SS>>) -> Unsub<'static, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn smoke()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1, out2) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.delay(Duration::from_millis(50), sch.clone()).subscribe(
+            move |v| out.lock().unwrap().push_str(&format!("{}", v)),
+            move |_e| out1.lock().unwrap().push_str("ok")
+        );
+
+        i.next(1);
+        i.next(2);
+        i.complete();
+        assert_eq!(out2.lock().unwrap().as_str(), "");
+
+        ::std::thread::sleep(Duration::from_millis(200));
+        assert_eq!(out2.lock().unwrap().as_str(), "12ok");
+    }
+
+    #[test]
+    fn selector_keeps_order()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, u64>::new()).clones();
+
+        o.delay_by(sch.clone(), |v: &u64| Duration::from_millis(*v * 30)).subscribe(
+            move |v| out.lock().unwrap().push_str(&format!("{}", v)), ()
+        );
+
+        i.next(3);
+        i.next(1);
+        i.next(2);
+
+        ::std::thread::sleep(Duration::from_millis(300));
+        assert_eq!(out1.lock().unwrap().as_str(), "312");
+    }
+
+    #[test]
+    fn error()
+    {
+        for &(delay_error, expected) in [(false, "err"), (true, "1err")].iter() {
+            let (sch, sch1) = Arc::new(CurrentThreadScheduler::new()).clones();
+            let (out, out1, out2) = Rc::new(RefCell::new(String::new())).clones();
+            let o = Rc::new(iter_once(vec![1, 2].into_iter()));
+            o.clone().subscribe((), ());
+
+            sch.schedule(None, move || {
+                iter_clone(1..2).merge(o).delay(Duration::from_millis(1), sch1).delay_error(delay_error).subscribe(
+                    move |v| out.borrow_mut().push_str(&format!("{}", v)),
+                    move |e: Option<RxError>| out1.borrow_mut().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" })
+                )
+            });
+
+            assert_eq!(out2.borrow().as_str(), expected);
+        }
+    }
+
+    #[test]
+    fn unsub()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        let sub = o.into_dyn().delay(Duration::from_millis(30), sch.clone()).subscribe(
+            move |v| out.lock().unwrap().push_str(&format!("{}", v)), ()
+        );
+
+        i.next(1);
+        i.next(2);
+        sub.unsub();
+
+        ::std::thread::sleep(Duration::from_millis(100));
+        assert_eq!(out1.lock().unwrap().as_str(), "");
+    }
+
+    #[test]
+    fn current_thread()
+    {
+        let (sch, sch1) = Arc::new(CurrentThreadScheduler::new()).clones();
+        let (out, out1, out2) = Rc::new(RefCell::new(String::new())).clones();
+
+        sch.schedule(None, move || {
+            iter_clone(0..3).delay(Duration::from_millis(1), sch1).subscribe(
+                move |v| out.borrow_mut().push_str(&format!("{}", v)),
+                move |_e| out1.borrow_mut().push_str("ok")
+            )
+        });
+
+        assert_eq!(out2.borrow().as_str(), "012ok");
+    }
+
+    #[test]
+    fn event_loop_unsub()
+    {
+        let sch = Arc::new(EventLoopScheduler::new(Arc::new(DefaultThreadFac), false));
+        let (out, out1, out2) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        let sub = o.delay(Duration::from_millis(30), sch.clone()).subscribe(
+            move |v| out.lock().unwrap().push_str(&format!("{}", v)),
+            move |_e| out1.lock().unwrap().push_str("ok")
+        );
+
+        //all items are queued on the loop before any is due
+        for v in 0..5 { i.next(v); }
+        i.complete();
+        sub.unsub();
+
+        ::std::thread::sleep(Duration::from_millis(150));
+        assert_eq!(out2.lock().unwrap().as_str(), "");
+    }
+}
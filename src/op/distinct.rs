@@ -0,0 +1,172 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+pub struct DistinctOp<'o, SS:YesNo, VBy, Src, F, K, SVBy: RefOrVal>
+{
+    f: Arc<F>,
+    flush: Option<DynObservable<'o, 'o, SS, SVBy>>,
+    src: Src,
+    PhantomData: PhantomData<(AnySendSync<VBy>, AnySendSync<K>)>
+}
+
+pub trait ObsDistinctOp<'o, SS: YesNo, VBy: RefOrVal> : Sized
+{
+    fn distinct(self) -> DistinctOp<'o, SS, VBy, Self, Identity, VBy::RAW, ()>
+    { DistinctOp{ f: Arc::new(Identity), flush: None, src: self, PhantomData } }
+
+    fn distinct_by<K, F: Act<SS, Ref<VBy::RAW>, K>>(self, f: F) -> DistinctOp<'o, SS, VBy, Self, F, K, ()>
+    { DistinctOp{ f: Arc::new(f), flush: None, src: self, PhantomData } }
+
+    //the set of seen keys is cleared every time `flush` emits
+    fn distinct_by_flush<K, F: Act<SS, Ref<VBy::RAW>, K>, SVBy: RefOrVal>(self, f: F, flush: impl Observable<'o, SS, SVBy>+'o) -> DistinctOp<'o, SS, VBy, Self, F, K, SVBy>
+    { DistinctOp{ f: Arc::new(f), flush: Some(flush.into_dyn()), src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>>
+ObsDistinctOp<'o, SS, VBy> for Src {}
+
+pub trait DynObsDistinctOp<'o, SS: YesNo, VBy: RefOrVal+'o>
+{
+    fn distinct(self) -> DynObservable<'o, 'o, SS, VBy> where VBy::RAW: Clone+Hash+Eq+'o;
+    fn distinct_by<K: Hash+Eq+'o, F: Act<SS, Ref<VBy::RAW>, K>+'o>(self, f: F) -> DynObservable<'o, 'o, SS, VBy>;
+    fn distinct_by_flush<K: Hash+Eq+'o, F: Act<SS, Ref<VBy::RAW>, K>+'o, SVBy: RefOrVal+'o>(self, f: F, flush: impl Observable<'o, SS, SVBy>+'o) -> DynObservable<'o, 'o, SS, VBy>;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o>
+DynObsDistinctOp<'o, SS, VBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn distinct(self) -> DynObservable<'o, 'o, SS, VBy> where VBy::RAW: Clone+Hash+Eq+'o
+    { DistinctOp::<SS, VBy, _, _, _, ()>{ f: Arc::new(Identity), flush: None, src: self.src, PhantomData }.into_dyn() }
+
+    fn distinct_by<K: Hash+Eq+'o, F: Act<SS, Ref<VBy::RAW>, K>+'o>(self, f: F) -> DynObservable<'o, 'o, SS, VBy>
+    { DistinctOp::<SS, VBy, _, _, _, ()>{ f: Arc::new(f), flush: None, src: self.src, PhantomData }.into_dyn() }
+
+    fn distinct_by_flush<K: Hash+Eq+'o, F: Act<SS, Ref<VBy::RAW>, K>+'o, SVBy: RefOrVal+'o>(self, f: F, flush: impl Observable<'o, SS, SVBy>+'o) -> DynObservable<'o, 'o, SS, VBy>
+    { DistinctOp{ f: Arc::new(f), flush: Some(flush.into_dyn()), src: self.src, PhantomData }.into_dyn() }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct DistinctState<'o, SS:YesNo, N, EC, K>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    seen: UnsafeCell<HashSet<K>>,
+}
+
+impl<'o, SS:YesNo, N, EC: ActEc<'o, SS>, K> DistinctState<'o, SS, N, EC, K>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>, K: Hash+Eq+'o, F: Act<SS, Ref<VBy::RAW>, K>+'o, SVBy: RefOrVal+'o>
+Observable<'o, SS, VBy>
+for DistinctOp<'o, SS, VBy, Src, F, K, SVBy>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, VBy>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let f = act_sendsync(self.f.clone());
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(DistinctState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), seen: UnsafeCell::new(HashSet::new())
+        }) });
+
+        if let Some(flush) = self.flush.as_ref() {
+            sub.add(flush.subscribe(
+                forward_next((), SSWrap::new(state.clone()), |(), state, _v: SVBy| {
+                    state.sub.if_not_done(|| unsafe{ &mut *state.seen.get() }.clear());
+                }, |(), state| state.sub.is_done()),
+
+                forward_ec(SSWrap::new(state.clone()), |state, e: Option<RxError>| {
+                    if e.is_some() { state.sub.if_not_done(|| state.end(e)); }
+                })
+            ));
+
+            if sub.is_done() { return sub; }
+        }
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), (SSWrap::new(state.clone()), f), |(), (state, f), v: VBy| {
+                state.sub.if_not_done(|| {
+                    if unsafe{ &mut *state.seen.get() }.insert(f.call(v.as_ref())) {
+                        state.next.call(v.into_v());
+                    }
+                });
+            }, |(), (state, _)| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| state.end(e));
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, VBy>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(vec![1, 2, 1, 3, 2, 4].into_iter()).distinct().subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        assert_eq!(out.borrow().as_str(), "1234ok");
+    }
+
+    #[test]
+    fn flush()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, String>::new()).clones();
+        let (s, sig) = Rc::new(Subject::<NO, ()>::new()).clones();
+
+        o.into_dyn().distinct_by_flush(|v: &String| v.len(), sig).subscribe(|v: &String| out.borrow_mut().push_str(&format!("{},", v)), ());
+
+        i.next("a".to_owned());
+        i.next("b".to_owned());
+        i.next("cc".to_owned());
+        s.next(());
+        i.next("d".to_owned());
+        i.next("ee".to_owned());
+        i.next("f".to_owned());
+        assert_eq!(out.borrow().as_str(), "a,cc,d,ee,");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.distinct_by(|v: &i32| v % 3).subscribe(move |v:&_| out.lock().unwrap().push(*v), ());
+
+        ::std::thread::spawn(move || {
+            for v in 0..10 { i.next(v); }
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+
+//the key selector of `distinct_until_changed` & `distinct`: the value itself
+pub struct Identity;
+
+unsafe impl<SS:YesNo, V: Clone>
+Act<SS, Ref<V>, V>
+for Identity
+{
+    #[inline(always)] fn call(&self, v: *const V) -> V { unsafe{ &*v }.clone() }
+}
+
+pub struct DistinctUntilChangedOp<SS, VBy, Src, F, K>
+{
+    f: Arc<F>,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>, AnySendSync<K>)>
+}
+
+pub trait ObsDistinctUntilChangedOp<SS: YesNo, VBy: RefOrVal> : Sized
+{
+    fn distinct_until_changed(self) -> DistinctUntilChangedOp<SS, VBy, Self, Identity, VBy::RAW>
+    { DistinctUntilChangedOp{ f: Arc::new(Identity), src: self, PhantomData } }
+
+    fn distinct_until_changed_by<K, F: Act<SS, Ref<VBy::RAW>, K>>(self, f: F) -> DistinctUntilChangedOp<SS, VBy, Self, F, K>
+    { DistinctUntilChangedOp{ f: Arc::new(f), src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>>
+ObsDistinctUntilChangedOp<SS, VBy> for Src {}
+
+pub trait DynObsDistinctUntilChangedOp<'o, SS: YesNo, VBy: RefOrVal+'o>
+{
+    fn distinct_until_changed(self) -> DynObservable<'o, 'o, SS, VBy> where VBy::RAW: Clone+PartialEq+'o;
+    fn distinct_until_changed_by<K: PartialEq+'o, F: Act<SS, Ref<VBy::RAW>, K>+'o>(self, f: F) -> DynObservable<'o, 'o, SS, VBy>;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o>
+DynObsDistinctUntilChangedOp<'o, SS, VBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn distinct_until_changed(self) -> DynObservable<'o, 'o, SS, VBy> where VBy::RAW: Clone+PartialEq+'o
+    { DistinctUntilChangedOp{ f: Arc::new(Identity), src: self.src, PhantomData }.into_dyn() }
+
+    fn distinct_until_changed_by<K: PartialEq+'o, F: Act<SS, Ref<VBy::RAW>, K>+'o>(self, f: F) -> DynObservable<'o, 'o, SS, VBy>
+    { DistinctUntilChangedOp{ f: Arc::new(f), src: self.src, PhantomData }.into_dyn() }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>, K: PartialEq+'o, F: Act<SS, Ref<VBy::RAW>, K>+'o>
+Observable<'o, SS, VBy>
+for DistinctUntilChangedOp<SS, VBy, Src, F, K>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, VBy>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let next = SSActNextWrap::new(next);
+        let f = act_sendsync(self.f.clone());
+        let sub = Unsub::new();
+        //no mutex here because access is protected by Unsub's internal lock
+        let last = unsafe{ AnySendSync::new(UnsafeCell::new(None)) };
+
+        sub.clone().added_each(self.src.subscribe(forward_next(next, (sub, f, SSWrap::new(last)), |next, (sub, f, last), v: VBy| {
+            sub.if_not_done(|| {
+                let last = unsafe{ &mut *last.get() };
+                let key = f.call(v.as_ref());
+                if last.as_ref() != Some(&key) {
+                    last.replace(key);
+                    next.call(v.into_v());
+                }
+            });
+        }, |s, _| s.stopped()), ec))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, VBy>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(vec![1, 1, 2, 2, 2, 1, 3, 3].into_iter()).distinct_until_changed().subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        assert_eq!(out.borrow().as_str(), "1213ok");
+    }
+
+    #[test]
+    fn behavior_subject()
+    {
+        let out = RefCell::new(String::new());
+        let (s, s1) = Rc::new(BehaviorSubject::<NO, i32>::new(1)).clones();
+
+        s.into_dyn().distinct_until_changed().subscribe(|v:&_| out.borrow_mut().push_str(&format!("{}", v)), ());
+
+        s1.next(1);
+        s1.next(2);
+        s1.next(2);
+        s1.next(1);
+        assert_eq!(out.borrow().as_str(), "121");
+    }
+
+    #[test]
+    fn by_key()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(vec!["a", "b", "cc", "dd", "e"].into_iter()).distinct_until_changed_by(|v: &&str| v.len()).subscribe(
+            |v| out.borrow_mut().push_str(v), ()
+        );
+
+        assert_eq!(out.borrow().as_str(), "acce");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.distinct_until_changed().subscribe(move |v:&_| out.lock().unwrap().push(*v), ());
+
+        ::std::thread::spawn(move || {
+            for v in vec![1, 1, 2, 3, 3] { i.next(v); }
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), vec![1, 2, 3]);
+    }
+}
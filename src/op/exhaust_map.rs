@@ -0,0 +1,94 @@
+use crate::*;
+use super::single_inner::{OnBusy, subscribe_single_inner};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+pub struct ExhaustMapOp<SS, VBy, Src, F, O>
+{
+    f: Arc<F>,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>, AnySendSync<O>)>
+}
+
+pub trait ObsExhaustMapOp<'o, SS: YesNo, VBy: RefOrVal, O, F: Act<SS, VBy, O>+'o> : Sized
+{
+    fn exhaust_map(self, f: F) -> ExhaustMapOp<SS, VBy, Self, F, O> { ExhaustMapOp{ f: Arc::new(f), src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, O, Src: Observable<'o, SS, VBy>+'o, F: Act<SS, VBy, O>+'o>
+ObsExhaustMapOp<'o, SS, VBy, O, F> for Src {}
+
+pub trait DynObsExhaustMapOp<'o, SS: YesNo, VBy: RefOrVal+'o, By: RefOrVal+'o, O: Observable<'o, SS, By>+'o, F: Act<SS, VBy, O>+'o>
+{
+    fn exhaust_map(self, f: F) -> DynObservable<'o, 'o, SS, By>;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, By: RefOrVal+'o, O: Observable<'o, SS, By>+'o, F: Act<SS, VBy, O>+'o>
+DynObsExhaustMapOp<'o, SS, VBy, By, O, F>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn exhaust_map(self, f: F) -> DynObservable<'o, 'o, SS, By>
+    { ExhaustMapOp{ f: Arc::new(f), src: self.src, PhantomData }.into_dyn() }
+}
+
+impl<'s, 'o, SS:YesNo, VBy: RefOrVal+'o, By: RefOrVal+'o, O: Observable<'o, SS, By>+'o, Src: Observable<'o, SS, VBy>+'s, F: Act<SS, VBy, O>+'o>
+Observable<'o, SS, By>
+for ExhaustMapOp<SS, VBy, Src, F, O>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, By>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    { subscribe_single_inner(&self.src, &self.f, OnBusy::Drop, next, ec) }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, By>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (a, a1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (b, b1) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.exhaust_map(move |v:&i32| if *v == 0 { a.clone() } else { b.clone() }).subscribe(
+            |v:&_| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        i.next(0);
+        i.next(1);
+        a1.next(1);
+        b1.next(2);
+        a1.complete();
+
+        i.next(1);
+        b1.next(3);
+        i.complete();
+        assert_eq!(out.borrow().as_str(), "13");
+
+        b1.complete();
+        assert_eq!(out.borrow().as_str(), "13ok");
+    }
+
+    #[test]
+    fn sync_inner()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(1..4).into_dyn().exhaust_map(|v| iter_clone(0..v)).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        assert_eq!(out.borrow().as_str(), "001012ok");
+    }
+}
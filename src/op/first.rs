@@ -0,0 +1,237 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::Cell;
+use std::cell::UnsafeCell;
+
+pub struct FirstOp<SS, Src>
+{
+    index: usize,
+    err: SequenceError,
+    src: Src,
+    PhantomData: PhantomData<(SS)>
+}
+
+pub struct FirstOrOp<SS, VBy: RefOrVal, Src>
+{
+    default: VBy::RAW,
+    src: Src,
+    PhantomData: PhantomData<(SS)>
+}
+
+pub trait ObsFirstOp<SS: YesNo, VBy: RefOrVal> : Sized
+{
+    fn first(self) -> FirstOp<SS, Self> { FirstOp{ index: 0, err: SequenceError::NoElements, src: self, PhantomData } }
+    fn element_at(self, index: usize) -> FirstOp<SS, Self> { FirstOp{ index, err: SequenceError::ArgumentOutOfRange, src: self, PhantomData } }
+
+    fn first_or(self, default: VBy::RAW) -> FirstOrOp<SS, VBy, Self> where VBy: IntoOwned, VBy::RAW: Clone
+    { FirstOrOp{ default, src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>>
+ObsFirstOp<SS, VBy> for Src {}
+
+pub trait DynObsFirstOp<'o, SS: YesNo, VBy: RefOrVal+'o>
+{
+    fn first(self) -> Self;
+    fn element_at(self, index: usize) -> Self;
+
+    fn first_or(self, default: VBy::RAW) -> DynObservable<'o, 'o, SS, Val<VBy::RAW>> where VBy: IntoOwned, VBy::RAW: Clone+'o;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o>
+DynObsFirstOp<'o, SS, VBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn first(self) -> Self
+    { FirstOp{ index: 0, err: SequenceError::NoElements, src: self.src, PhantomData }.into_dyn() }
+
+    fn element_at(self, index: usize) -> Self
+    { FirstOp{ index, err: SequenceError::ArgumentOutOfRange, src: self.src, PhantomData }.into_dyn() }
+
+    fn first_or(self, default: VBy::RAW) -> DynObservable<'o, 'o, SS, Val<VBy::RAW>> where VBy: IntoOwned, VBy::RAW: Clone+'o
+    { FirstOrOp{ default, src: self.src, PhantomData }.into_dyn() }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, VBy>
+for FirstOp<SS, Src>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, VBy>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let next = SSActNextWrap::new(next);
+        let sub = Unsub::new();
+        //no mutex here because access is protected by Unsub's internal lock
+        let state = Arc::new(unsafe{ AnySendSync::new((Cell::new(self.index), self.err, UnsafeCell::new(Some(ec)))) });
+
+        sub.clone().added(self.src.subscribe(
+            forward_next(next, (sub.clone(), SSWrap::new(state.clone())), |next, (sub, state), v: VBy| {
+                sub.if_not_done(|| {
+                    let index = state.0.get();
+                    if index != 0 {
+                        state.0.replace(index - 1);
+                        return;
+                    }
+                    next.call(v.into_v());
+                    sub.unsub_then(|| unsafe{ &mut *state.2.get() }.take().map_or((), |ec| ec.call_once(None)));
+                });
+            }, |s, (sub, _)| s.stopped() || sub.is_done()),
+
+            forward_ec((sub, SSWrap::new(state)), |(sub, state), e: Option<RxError>| {
+                sub.unsub_then(|| unsafe{ &mut *state.2.get() }.take().map_or((), |ec| ec.call_once(e.or_else(|| Some(RxError::sequence(state.1))))));
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, VBy>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct FirstOrState<'o, SS:YesNo, N, EC, V>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    default: UnsafeCell<Option<V>>,
+}
+
+impl<'o, SS:YesNo, N: ActNext<'o, SS, Val<V>>, EC: ActEc<'o, SS>, V> FirstOrState<'o, SS, N, EC, V>
+{
+    fn end(&self, v: Option<V>, e: Option<RxError>)
+    {
+        let v = if e.is_none() { v.or_else(|| unsafe{ &mut *self.default.get() }.take()) } else { None };
+        self.sub.unsub_then(|| {
+            if let Some(v) = v {
+                if !self.next.stopped() { self.next.call(v); }
+            }
+            unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e))
+        });
+    }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<VBy::RAW>>
+for FirstOrOp<SS, VBy, Src>
+where VBy::RAW: Clone+'o
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<VBy::RAW>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(FirstOrState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), default: UnsafeCell::new(Some(self.default.clone()))
+        }) });
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| state.end(Some(v.into_owned()), None));
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| state.end(None, e));
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<VBy::RAW>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(1..10).first().subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "1ok");
+
+        let out = RefCell::new(String::new());
+        iter_clone(1..10).into_dyn().element_at(3).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "4ok");
+    }
+
+    #[test]
+    fn empty()
+    {
+        let out = RefCell::new(String::new());
+
+        Of::<NO, i32>::empty().first().subscribe(
+            |v: &i32| out.borrow_mut().push_str(&format!("{}", v)),
+            |e: Option<RxError>| out.borrow_mut().push_str(&format!("{:?}", e.unwrap().set_handled().sequence_error()))
+        );
+        assert_eq!(out.borrow().as_str(), "Some(NoElements)");
+
+        let out = RefCell::new(String::new());
+        iter_clone(1..3).element_at(2).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |e: Option<RxError>| out.borrow_mut().push_str(&format!("{:?}", e.unwrap().set_handled().sequence_error()))
+        );
+        assert_eq!(out.borrow().as_str(), "Some(ArgumentOutOfRange)");
+    }
+
+    #[test]
+    fn first_or()
+    {
+        let out = RefCell::new(String::new());
+
+        Of::<NO, i32>::empty().first_or(7).subscribe(
+            |v: i32| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "7ok");
+
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, String>::new()).clones();
+
+        o.into_dyn().first_or("z".to_owned()).subscribe(
+            |v: String| out.borrow_mut().push_str(&v),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        i.next("a".to_owned());
+        i.next("b".to_owned());
+        assert_eq!(out.borrow().as_str(), "aok");
+    }
+
+    #[test]
+    fn stops_upstream()
+    {
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let sub = o.first().subscribe(|_: &i32| {}, ());
+
+        assert!(!sub.is_done());
+        i.next(1);
+        assert!(sub.is_done());
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.element_at(5).subscribe(move |v:&_| out.lock().unwrap().push(*v), ());
+
+        ::std::thread::spawn(move || {
+            for v in 0..10 { i.next(v); }
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), vec![5]);
+    }
+}
@@ -0,0 +1,324 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::Cell;
+use std::cell::UnsafeCell;
+use std::collections::VecDeque;
+use std::collections::HashMap;
+
+pub struct FlatMapOp<SS, VBy, Src, F, O>
+{
+    f: Arc<F>,
+    max: usize,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>, AnySendSync<O>)>
+}
+
+pub trait ObsFlatMapOp<'o, SS: YesNo, VBy: RefOrVal, O, F: Act<SS, VBy, O>+'o> : Sized
+{
+    fn flat_map(self, f: F) -> FlatMapOp<SS, VBy, Self, F, O> { self.flat_map_max(usize::max_value(), f) }
+    //`max == 0` means unbounded
+    fn flat_map_max(self, max: usize, f: F) -> FlatMapOp<SS, VBy, Self, F, O> { FlatMapOp{ f: Arc::new(f), max: unbounded_if_zero(max), src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, O, Src: Observable<'o, SS, VBy>+'o, F: Act<SS, VBy, O>+'o>
+ObsFlatMapOp<'o, SS, VBy, O, F> for Src {}
+
+pub trait DynObsFlatMapOp<'o, SS: YesNo, VBy: RefOrVal+'o, By: RefOrVal+'o, O: Observable<'o, SS, By>+'o, F: Act<SS, VBy, O>+'o>
+{
+    fn flat_map(self, f: F) -> DynObservable<'o, 'o, SS, By>;
+    fn flat_map_max(self, max: usize, f: F) -> DynObservable<'o, 'o, SS, By>;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, By: RefOrVal+'o, O: Observable<'o, SS, By>+'o, F: Act<SS, VBy, O>+'o>
+DynObsFlatMapOp<'o, SS, VBy, By, O, F>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn flat_map(self, f: F) -> DynObservable<'o, 'o, SS, By>
+    { self.flat_map_max(usize::max_value(), f) }
+
+    fn flat_map_max(self, max: usize, f: F) -> DynObservable<'o, 'o, SS, By>
+    { FlatMapOp{ f: Arc::new(f), max: unbounded_if_zero(max), src: self.src, PhantomData }.into_dyn() }
+}
+
+fn unbounded_if_zero(max: usize) -> usize { if max == 0 { usize::max_value() } else { max } }
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct FlatMapState<'o, SS:YesNo, N, EC, O>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    max: usize,
+    ec: UnsafeCell<Option<EC>>,
+    active: Cell<usize>,
+    src_done: Cell<bool>,
+    queue: UnsafeCell<VecDeque<O>>,
+    next_id: Cell<usize>,
+    inners: UnsafeCell<HashMap<usize, Unsub<'o, SS>>>,
+}
+
+impl<'o, SS:YesNo, N, EC: ActEc<'o, SS>, O> FlatMapState<'o, SS, N, EC, O>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+}
+
+fn subscribe_inner<'o, SS:YesNo, By: RefOrVal+'o, N: ActNext<'o, SS, By>, EC: ActEc<'o, SS>, O: Observable<'o, SS, By>+'o>
+(state: &Arc<AnySendSync<FlatMapState<'o, SS, N, EC, O>>>, o: O)
+{
+    //registered before subscribing, so an inner that completes synchronously is dropped right away
+    let id = state.next_id.get();
+    state.next_id.replace(id + 1);
+    let inner = Unsub::new();
+    unsafe{ &mut *state.inners.get() }.insert(id, inner.clone());
+
+    inner.add(o.subscribe(
+        forward_next((), SSWrap::new(state.clone()), |(), state, v: By| {
+            state.sub.if_not_done(|| state.next.call(v.into_v()));
+        }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+        forward_ec(SSWrap::new((state.clone(), id)), |caps, e: Option<RxError>| {
+            let (state, id) = caps.into_inner();
+            state.sub.if_not_done(|| {
+                if e.is_some() {
+                    return state.end(e);
+                }
+
+                if let Some(inner) = unsafe{ &mut *state.inners.get() }.remove(&id) {
+                    inner.unsub();
+                }
+
+                state.active.replace(state.active.get() - 1);
+                if let Some(o) = unsafe{ &mut *state.queue.get() }.pop_front() {
+                    state.active.replace(state.active.get() + 1);
+                    subscribe_inner(&state, o);
+                } else if state.active.get() == 0 && state.src_done.get() {
+                    state.end(None);
+                }
+            });
+        })
+    ));
+}
+
+impl<'s, 'o, SS:YesNo, VBy: RefOrVal+'o, By: RefOrVal+'o, O: Observable<'o, SS, By>+'o, Src: Observable<'o, SS, VBy>+'s, F: Act<SS, VBy, O>+'o>
+Observable<'o, SS, By>
+for FlatMapOp<SS, VBy, Src, F, O>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, By>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let f = act_sendsync(self.f.clone());
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(FlatMapState{
+            sub: sub.clone(), next, max: self.max,
+            ec: UnsafeCell::new(Some(ec)), active: Cell::new(0), src_done: Cell::new(false), queue: UnsafeCell::new(VecDeque::new()),
+            next_id: Cell::new(0), inners: UnsafeCell::new(HashMap::new())
+        }) });
+
+        sub.add(Unsub::with(forward_act_once(SSWrap::new(state.clone()), |state, ()| {
+            let inners = ::std::mem::replace(unsafe{ &mut *state.inners.get() }, HashMap::new());
+            for (_, inner) in inners { inner.unsub(); }
+        })));
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), (SSWrap::new(state.clone()), f), |(), (state, f), v: VBy| {
+                state.sub.if_not_done(|| {
+                    let o = f.call(v.into_v());
+                    if state.active.get() < state.max {
+                        state.active.replace(state.active.get() + 1);
+                        subscribe_inner(&*state, o);
+                    } else {
+                        unsafe{ &mut *state.queue.get() }.push_back(o);
+                    }
+                });
+            }, |(), (state, _)| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| {
+                    if e.is_some() {
+                        return state.end(e);
+                    }
+
+                    state.src_done.replace(true);
+                    if state.active.get() == 0 {
+                        state.end(None);
+                    }
+                });
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, By>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::Cell;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    //completes synchronously, but keeps a live subscription until unsubscribed
+    struct Live(Rc<Cell<usize>>);
+
+    impl Observable<'static, NO, Val<i32>> for Live
+    {
+        fn subscribe(&self, next: impl ActNext<'static, NO, Val<i32>>, ec: impl ActEc<'static, NO>) -> Unsub<'static, NO> where Self: Sized
+        {
+            let live = self.0.clone();
+            live.replace(live.get() + 1);
+            next.call(1);
+            ec.call_once(None);
+            Unsub::with(move || { live.replace(live.get() - 1); })
+        }
+
+        fn subscribe_dyn(&self, next: Box<ActNext<'static, NO, Val<i32>>>, ec: Box<ActEcBox<'static, NO>>) -> Unsub<'static, NO>
+        { self.subscribe(next, ec) }
+    }
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.flat_map(|v:&i32| iter_clone(0..*v)).subscribe(|v| out.borrow_mut().push_str(&format!("{}", v)), |_e| out.borrow_mut().push_str("ok"));
+
+        i.next(1);
+        i.next(2);
+        i.next(3);
+        assert_eq!(out.borrow().as_str(), "001012");
+
+        i.complete();
+        assert_eq!(out.borrow().as_str(), "001012ok");
+    }
+
+    #[test]
+    fn waits_for_inners()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (a, a1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (b, b1) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.into_dyn().flat_map(move |v:&i32| if *v == 0 { a.clone() } else { b.clone() }).subscribe(
+            |v:&_| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        i.next(0);
+        i.next(1);
+        a1.next(1);
+        b1.next(2);
+        a1.next(3);
+
+        i.complete();
+        a1.complete();
+        assert_eq!(out.borrow().as_str(), "123");
+
+        b1.next(4);
+        b1.complete();
+        assert_eq!(out.borrow().as_str(), "1234ok");
+    }
+
+    #[test]
+    fn max_concurrency()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let subjects = vec![Rc::new(Subject::<NO, i32>::new()), Rc::new(Subject::<NO, i32>::new()), Rc::new(Subject::<NO, i32>::new())];
+        let s = subjects.clone();
+
+        o.flat_map_max(1, move |v:&i32| s[*v as usize].clone()).subscribe(
+            |v:&_| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        i.next(0);
+        i.next(1);
+        i.next(2);
+        i.complete();
+
+        subjects[1].next(1);
+        subjects[0].next(0);
+        subjects[0].complete();
+        subjects[1].next(1);
+        subjects[1].complete();
+        subjects[2].next(2);
+        assert_eq!(out.borrow().as_str(), "012");
+
+        subjects[2].complete();
+        assert_eq!(out.borrow().as_str(), "012ok");
+    }
+
+    #[test]
+    fn zero_max_is_unbounded()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.flat_map_max(0, |v:&i32| Of::value(*v)).subscribe(
+            |v:&_| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        i.next(1);
+        i.next(2);
+        i.complete();
+        assert_eq!(out.borrow().as_str(), "12ok");
+    }
+
+    #[test]
+    fn releases_completed_inners()
+    {
+        let (live, live1) = Rc::new(Cell::new(0)).clones();
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        let sub = o.flat_map(move |_:&i32| Live(live.clone())).subscribe(|_| {}, |_e| {});
+
+        for _ in 0..3 { i.next(0); }
+        assert!(!sub.is_done());
+        assert_eq!(live1.get(), 0);
+    }
+
+    #[test]
+    fn error()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let inner = Rc::new(iter_once(vec![1, 2].into_iter()));
+
+        o.flat_map(move |_:&i32| inner.clone()).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |e: Option<RxError>| out.borrow_mut().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" })
+        );
+
+        i.next(0);
+        i.next(0);
+        i.next(0);
+        assert_eq!(out.borrow().as_str(), "12err");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (_sch, sch1) = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac))).clones();
+        let (out, out1, out2) = Arc::new(Mutex::new(0)).clones();
+
+        Of::<YES, usize>::value(3).flat_map(move |v:&usize| Timer::new(Duration::from_millis(10), sch1.clone()).take(*v)).subscribe(
+            move |v| *out.lock().unwrap() += v + 1,
+            move |_e| *out1.lock().unwrap() += 100
+        );
+
+        ::std::thread::sleep(Duration::from_millis(300));
+        assert_eq!(*out2.lock().unwrap(), 106);
+    }
+}
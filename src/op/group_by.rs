@@ -0,0 +1,304 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub struct GroupedObservable<'o, SS:YesNo, K, V>
+{
+    pub key: K,
+    obs: DynObservable<'o, 'o, SS, Ref<V>>,
+}
+
+impl<'o, SS:YesNo, K: Clone, V> Clone for GroupedObservable<'o, SS, K, V>
+{
+    fn clone(&self) -> Self { GroupedObservable{ key: self.key.clone(), obs: self.obs.clone() } }
+}
+
+impl<'o, SS:YesNo, K, V:'o>
+Observable<'o, SS, Ref<V>>
+for GroupedObservable<'o, SS, K, V>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Ref<V>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    { self.obs.subscribe(next, ec) }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Ref<V>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.obs.subscribe_dyn(next, ec) }
+}
+
+pub struct GroupByOp<SS, VBy, Src, K, F>
+{
+    f: Arc<F>,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>, AnySendSync<K>)>
+}
+
+pub struct GroupByUntilOp<SS, VBy, Src, K, F, D, O, DBy>
+{
+    f: Arc<F>,
+    duration: Arc<D>,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>, AnySendSync<K>, AnySendSync<O>, AnySendSync<DBy>)>
+}
+
+pub trait ObsGroupByOp<SS: YesNo, VBy: RefOrVal, K, F: Act<SS, Ref<VBy::RAW>, K>> : Sized
+{
+    fn group_by(self, f: F) -> GroupByOp<SS, VBy, Self, K, F>
+    { GroupByOp{ f: Arc::new(f), src: self, PhantomData } }
+
+    //a group completes and is forgotten once the observable `duration` returns for its key emits or completes
+    fn group_by_until<D: Act<SS, Ref<K>, O>, O, DBy>(self, f: F, duration: D) -> GroupByUntilOp<SS, VBy, Self, K, F, D, O, DBy>
+    { GroupByUntilOp{ f: Arc::new(f), duration: Arc::new(duration), src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, K: Hash+Eq+Clone+'o, Src: Observable<'o, SS, VBy>, F: Act<SS, Ref<VBy::RAW>, K>+'o>
+ObsGroupByOp<SS, VBy, K, F> for Src {}
+
+pub trait DynObsGroupByOp<'o, SS: YesNo, VBy: RefOrVal+'o, K: Hash+Eq+Clone+'o, F: Act<SS, Ref<VBy::RAW>, K>+'o>
+{
+    fn group_by(self, f: F) -> DynObservable<'o, 'o, SS, Val<GroupedObservable<'o, SS, K, VBy::RAW>>>;
+    fn group_by_until<D: Act<SS, Ref<K>, O>+'o, O: Observable<'o, SS, DBy>+'o, DBy: RefOrVal+'o>(self, f: F, duration: D) -> DynObservable<'o, 'o, SS, Val<GroupedObservable<'o, SS, K, VBy::RAW>>>;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, K: Hash+Eq+Clone+'o, F: Act<SS, Ref<VBy::RAW>, K>+'o>
+DynObsGroupByOp<'o, SS, VBy, K, F>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn group_by(self, f: F) -> DynObservable<'o, 'o, SS, Val<GroupedObservable<'o, SS, K, VBy::RAW>>>
+    { GroupByOp{ f: Arc::new(f), src: self.src, PhantomData }.into_dyn() }
+
+    fn group_by_until<D: Act<SS, Ref<K>, O>+'o, O: Observable<'o, SS, DBy>+'o, DBy: RefOrVal+'o>(self, f: F, duration: D) -> DynObservable<'o, 'o, SS, Val<GroupedObservable<'o, SS, K, VBy::RAW>>>
+    { GroupByUntilOp{ f: Arc::new(f), duration: Arc::new(duration), src: self.src, PhantomData }.into_dyn() }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock;
+//it's reentrant, so a group subscriber may still re-enter the source
+struct GroupByState<'o, SS:YesNo, N, EC, K, V>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    groups: UnsafeCell<HashMap<K, Arc<Subject<'o, SS, V>>>>,
+}
+
+impl<'o, SS:YesNo, N: ActNext<'o, SS, Val<GroupedObservable<'o, SS, K, V>>>, EC: ActEc<'o, SS>, K: Hash+Eq+Clone+'o, V: 'o> GroupByState<'o, SS, N, EC, K, V>
+{
+    fn new(sub: Unsub<'o, SS>, next: N, ec: EC) -> Self
+    {
+        GroupByState{ sub, next, ec: UnsafeCell::new(Some(ec)), groups: UnsafeCell::new(HashMap::new()) }
+    }
+
+    //forwards `v` to its group, returning the group if `v` just created it
+    fn push(&self, key: K, v: &V) -> Option<(K, Arc<Subject<'o, SS, V>>)>
+    {
+        let groups = unsafe{ &mut *self.groups.get() };
+        let (group, created) = match groups.get(&key) {
+            Some(group) => (group.clone(), false),
+            None => {
+                let group = Arc::new(Subject::new());
+                groups.insert(key.clone(), group.clone());
+                (group, true)
+            }
+        };
+
+        if created && !self.next.stopped() {
+            self.next.call(GroupedObservable{ key: key.clone(), obs: group.clone().into_dyn() });
+        }
+        group.next_ref(v);
+
+        if created { Some((key, group)) } else { None }
+    }
+
+    fn expire(&self, key: &K, group: &Arc<Subject<'o, SS, V>>)
+    {
+        let groups = unsafe{ &mut *self.groups.get() };
+        if groups.get(key).map_or(false, |g| Arc::ptr_eq(g, group)) { groups.remove(key); }
+        group.complete();
+    }
+
+    fn end(&self, e: Option<RxError>)
+    {
+        let groups: Vec<_> = unsafe{ &mut *self.groups.get() }.drain().map(|(_, g)| g).collect();
+        for group in groups { group.ec(e.clone()); }
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+}
+
+fn subscribe_duration<'o, SS:YesNo, N: ActNext<'o, SS, Val<GroupedObservable<'o, SS, K, V>>>+'o, EC: ActEc<'o, SS>+'o, K: Hash+Eq+Clone+'o, V: 'o, D: Act<SS, Ref<K>, O>, O: Observable<'o, SS, DBy>, DBy: RefOrVal+'o>
+(state: &Arc<AnySendSync<GroupByState<'o, SS, N, EC, K, V>>>, d: &D, key: K, group: Arc<Subject<'o, SS, V>>)
+{
+    let o = d.call(&key);
+    let dsub = Unsub::new();
+    let group = Arc::new(unsafe{ AnySendSync::new((key, group)) });
+
+    state.sub.add(dsub.clone());
+    dsub.add(o.subscribe(
+        forward_next((), (SSWrap::new(state.clone()), SSWrap::new(group.clone()), dsub.clone()), |(), (state, group, dsub), _v: DBy| {
+            state.sub.if_not_done(|| dsub.unsub_then(|| state.expire(&group.0, &group.1)));
+        }, |(), (state, _, dsub)| dsub.is_done() || state.sub.is_done()),
+
+        forward_ec((SSWrap::new(state.clone()), SSWrap::new(group), dsub.clone()), |(state, group, dsub), e: Option<RxError>| {
+            state.sub.if_not_done(|| dsub.unsub_then(|| if e.is_some() { state.end(e); } else { state.expire(&group.0, &group.1); }));
+        })
+    ));
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>, K: Hash+Eq+Clone+'o, F: Act<SS, Ref<VBy::RAW>, K>+'o>
+Observable<'o, SS, Val<GroupedObservable<'o, SS, K, VBy::RAW>>>
+for GroupByOp<SS, VBy, Src, K, F>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<GroupedObservable<'o, SS, K, VBy::RAW>>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let f = act_sendsync(self.f.clone());
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(GroupByState::new(sub.clone(), next, ec)) });
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), (SSWrap::new(state.clone()), f), |(), (state, f), v: VBy| {
+                state.sub.if_not_done(|| { state.push(f.call(v.as_ref()), v.as_ref()); });
+            }, |(), (state, _)| state.sub.is_done()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| state.sub.if_not_done(|| state.end(e)))
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<GroupedObservable<'o, SS, K, VBy::RAW>>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>, K: Hash+Eq+Clone+'o, F: Act<SS, Ref<VBy::RAW>, K>+'o, D: Act<SS, Ref<K>, O>+'o, O: Observable<'o, SS, DBy>+'o, DBy: RefOrVal+'o>
+Observable<'o, SS, Val<GroupedObservable<'o, SS, K, VBy::RAW>>>
+for GroupByUntilOp<SS, VBy, Src, K, F, D, O, DBy>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<GroupedObservable<'o, SS, K, VBy::RAW>>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let f = act_sendsync(self.f.clone());
+        let d = act_sendsync(self.duration.clone());
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(GroupByState::new(sub.clone(), next, ec)) });
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), (SSWrap::new(state.clone()), f, d), |(), (state, f, d), v: VBy| {
+                state.sub.if_not_done(|| if let Some((key, group)) = state.push(f.call(v.as_ref()), v.as_ref()) {
+                    subscribe_duration(&*state, d, key, group);
+                });
+            }, |(), (state, _, _)| state.sub.is_done()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| state.sub.if_not_done(|| state.end(e)))
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<GroupedObservable<'o, SS, K, VBy::RAW>>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = Rc::new(RefCell::new(String::new()));
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        let groups = Rc::new([RefCell::new(String::new()), RefCell::new(String::new())]);
+
+        let (out1, groups1) = (out.clone(), groups.clone());
+        o.group_by(|v: &i32| v % 2).subscribe(move |g: GroupedObservable<NO, i32, i32>| {
+            let (key, groups, groups1) = (g.key as usize, groups1.clone(), groups1.clone());
+            out1.borrow_mut().push_str(&format!("<{}>", key));
+            g.subscribe(move |v:&_| groups[key].borrow_mut().push_str(&format!("{},", v)), move |_e| groups1[key].borrow_mut().push_str("ok"));
+        }, |_e| out.borrow_mut().push_str("ok"));
+
+        i.next(1);
+        i.next(2);
+        i.next(3);
+        i.next(4);
+        assert_eq!(out.borrow().as_str(), "<1><0>");
+        assert_eq!(groups[0].borrow().as_str(), "2,4,");
+        assert_eq!(groups[1].borrow().as_str(), "1,3,");
+
+        i.complete();
+        assert_eq!(out.borrow().as_str(), "<1><0>ok");
+        assert_eq!(groups[0].borrow().as_str(), "2,4,ok");
+        assert_eq!(groups[1].borrow().as_str(), "1,3,ok");
+    }
+
+    #[test]
+    fn duration()
+    {
+        let out = Rc::new(RefCell::new(String::new()));
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (s, sig) = Rc::new(Subject::<NO, ()>::new()).clones();
+
+        let out1 = out.clone();
+        o.into_dyn().group_by_until(|v: &i32| v % 2, move |_k: &i32| sig.clone()).subscribe(move |g: GroupedObservable<NO, i32, i32>| {
+            let (out, out1) = out1.clone().clones();
+            out.borrow_mut().push_str("<");
+            g.subscribe(move |v:&_| out.borrow_mut().push_str(&format!("{}", v)), move |_e| out1.borrow_mut().push_str(">"));
+        }, ());
+
+        i.next(1);
+        i.next(3);
+        s.next(());
+        i.next(5);
+        assert_eq!(out.borrow().as_str(), "<13><5");
+    }
+
+    #[test]
+    fn reentrant_thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let (i, i1, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.group_by(|v: &i32| *v / 10).subscribe(move |g: GroupedObservable<YES, i32, i32>| {
+            let (out, i) = (out.clone(), i1.clone());
+            g.subscribe(move |v: &i32| {
+                out.lock().unwrap().push(*v);
+                //re-enters the source from inside a group subscriber
+                if *v < 30 { i.next(*v + 10); }
+            }, ());
+        }, ());
+
+        ::std::thread::spawn(move || i.next(1)).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), vec![1, 11, 21, 31]);
+    }
+
+    #[test]
+    fn duration_error_thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let (res, res1) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+        let (s, sig) = Arc::new(Subject::<YES, ()>::new()).clones();
+
+        o.group_by_until(|_v: &i32| 0, move |_k: &i32| sig.clone()).subscribe(move |g: GroupedObservable<YES, i32, i32>| {
+            let (out, out1) = out.clone().clones();
+            g.subscribe(move |v: &i32| out.lock().unwrap().push(*v), move |e: Option<RxError>| {
+                if let Some(e) = e { e.set_handled(); }
+                out1.lock().unwrap().push(-1);
+            });
+        }, move |e: Option<RxError>| res.lock().unwrap().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" }));
+
+        i.next(0);
+        let t = ::std::thread::spawn(move || s.error(RxError::simple(None, "error")));
+        for v in 1..10000 { i.next(v); }
+        t.join().ok();
+
+        //the group ends exactly once and receives nothing afterwards
+        let out = out1.lock().unwrap();
+        assert_eq!(out.iter().filter(|v| **v == -1).count(), 1);
+        assert_eq!(out.last(), Some(&-1));
+        assert_eq!(res1.lock().unwrap().as_str(), "err");
+    }
+}
@@ -0,0 +1,176 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+
+pub struct LastOp<SS, VBy, Src>
+{
+    single: bool,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>)>
+}
+
+pub trait ObsLastOp<SS: YesNo, VBy: RefOrVal> : Sized
+{
+    fn last(self) -> LastOp<SS, VBy, Self> { LastOp{ single: false, src: self, PhantomData } }
+    //errors as soon as a second value arrives
+    fn single(self) -> LastOp<SS, VBy, Self> { LastOp{ single: true, src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+ObsLastOp<SS, VBy> for Src {}
+
+pub trait DynObsLastOp<'o, SS: YesNo, VBy: IntoOwned+'o>
+{
+    fn last(self) -> DynObservable<'o, 'o, SS, Val<VBy::RAW>>;
+    fn single(self) -> DynObservable<'o, 'o, SS, Val<VBy::RAW>>;
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o>
+DynObsLastOp<'o, SS, VBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn last(self) -> DynObservable<'o, 'o, SS, Val<VBy::RAW>>
+    { LastOp{ single: false, src: self.src, PhantomData }.into_dyn() }
+
+    fn single(self) -> DynObservable<'o, 'o, SS, Val<VBy::RAW>>
+    { LastOp{ single: true, src: self.src, PhantomData }.into_dyn() }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct LastState<'o, SS:YesNo, N, EC, V>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    single: bool,
+    last: UnsafeCell<Option<V>>,
+}
+
+impl<'o, SS:YesNo, N: ActNext<'o, SS, Val<V>>, EC: ActEc<'o, SS>, V> LastState<'o, SS, N, EC, V>
+{
+    fn push(&self, v: V)
+    {
+        if unsafe{ &mut *self.last.get() }.replace(v).is_some() && self.single {
+            self.end(Some(RxError::sequence(SequenceError::MoreThanOneElement)));
+        }
+    }
+
+    fn end(&self, e: Option<RxError>)
+    {
+        let last = unsafe{ &mut *self.last.get() }.take();
+        let e = match (e, last) {
+            (None, Some(v)) => {
+                if !self.next.stopped() { self.next.call(v); }
+                None
+            },
+            (None, None) => Some(RxError::sequence(SequenceError::NoElements)),
+            (e, _) => e
+        };
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<VBy::RAW>>
+for LastOp<SS, VBy, Src>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<VBy::RAW>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(LastState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), single: self.single, last: UnsafeCell::new(None)
+        }) });
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| state.push(v.into_owned()));
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| state.end(e));
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<VBy::RAW>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(1..10).last().subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "9ok");
+
+        let out = RefCell::new(String::new());
+        Of::<NO, i32>::value(1).into_dyn().single().subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "1ok");
+    }
+
+    #[test]
+    fn empty()
+    {
+        let out = RefCell::new(String::new());
+
+        Of::<NO, i32>::empty().last().subscribe(
+            |v: i32| out.borrow_mut().push_str(&format!("{}", v)),
+            |e: Option<RxError>| out.borrow_mut().push_str(&format!("{:?}", e.unwrap().set_handled().sequence_error()))
+        );
+        assert_eq!(out.borrow().as_str(), "Some(NoElements)");
+    }
+
+    #[test]
+    fn single_too_many()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, String>::new()).clones();
+
+        let sub = o.single().subscribe(
+            |v: String| out.borrow_mut().push_str(&v),
+            |e: Option<RxError>| out.borrow_mut().push_str(&format!("{:?}", e.unwrap().set_handled().sequence_error()))
+        );
+
+        i.next("a".to_owned());
+        assert!(!sub.is_done());
+
+        i.next("b".to_owned());
+        assert!(sub.is_done());
+        assert_eq!(out.borrow().as_str(), "Some(MoreThanOneElement)");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.last().subscribe(move |v| out.lock().unwrap().push(v), ());
+
+        ::std::thread::spawn(move || {
+            for v in 0..10 { i.next(v); }
+            i.complete();
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), vec![9]);
+    }
+}
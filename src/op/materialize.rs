@@ -0,0 +1,288 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+
+//errors produced by `materialize` are marked handled: the notification owns them from then on;
+//`dematerialize` re-raises a clone, which downstream has to handle again
+#[derive(Debug)]
+pub enum Notification<V>
+{
+    Next(V),
+    Error(RxError),
+    Complete,
+}
+
+//`RxError::clone` is unhandled: a cloned notification keeps it handled, so it can be dropped like the original
+impl<V: Clone> Clone for Notification<V>
+{
+    fn clone(&self) -> Self
+    {
+        match self {
+            Notification::Next(v) => Notification::Next(v.clone()),
+            Notification::Error(e) => Notification::Error(e.clone().set_handled()),
+            Notification::Complete => Notification::Complete
+        }
+    }
+}
+
+pub struct MaterializeOp<SS, VBy, Src>
+{
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>)>
+}
+
+pub struct DematerializeOp<SS, VBy, Src>
+{
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>)>
+}
+
+pub trait ObsMaterializeOp<SS: YesNo, VBy: RefOrVal> : Sized
+{
+    fn materialize(self) -> MaterializeOp<SS, VBy, Self> { MaterializeOp{ src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+ObsMaterializeOp<SS, VBy> for Src {}
+
+pub trait ObsDematerializeOp<SS: YesNo, VBy: RefOrVal> : Sized
+{
+    fn dematerialize(self) -> DematerializeOp<SS, VBy, Self> { DematerializeOp{ src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, V: 'o, VBy: IntoOwned<RAW=Notification<V>>+'o, Src: Observable<'o, SS, VBy>>
+ObsDematerializeOp<SS, VBy> for Src {}
+
+pub trait DynObsMaterializeOp<'o, SS: YesNo, VBy: IntoOwned+'o>
+{
+    fn materialize(self) -> DynObservable<'o, 'o, SS, Val<Notification<VBy::RAW>>>;
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o>
+DynObsMaterializeOp<'o, SS, VBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn materialize(self) -> DynObservable<'o, 'o, SS, Val<Notification<VBy::RAW>>>
+    { MaterializeOp{ src: self.src, PhantomData }.into_dyn() }
+}
+
+pub trait DynObsDematerializeOp<'o, SS: YesNo, V: 'o>
+{
+    fn dematerialize(self) -> DynObservable<'o, 'o, SS, Val<V>>;
+}
+
+impl<'o, SS:YesNo, V: 'o, VBy: IntoOwned<RAW=Notification<V>>+'o>
+DynObsDematerializeOp<'o, SS, V>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn dematerialize(self) -> DynObservable<'o, 'o, SS, Val<V>>
+    { DematerializeOp{ src: self.src, PhantomData }.into_dyn() }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct NotificationState<'o, SS:YesNo, N, EC>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+}
+
+impl<'o, SS:YesNo, N, EC: ActEc<'o, SS>> NotificationState<'o, SS, N, EC>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<Notification<VBy::RAW>>>
+for MaterializeOp<SS, VBy, Src>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<Notification<VBy::RAW>>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(NotificationState{ sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)) }) });
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| state.next.call(Notification::Next(v.into_owned())));
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| {
+                    if !state.next.stopped() {
+                        state.next.call(e.map_or(Notification::Complete, |e| Notification::Error(e.set_handled())));
+                    } else if let Some(e) = e {
+                        e.set_handled();
+                    }
+                    state.end(None);
+                });
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<Notification<VBy::RAW>>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+impl<'o, SS:YesNo, V: 'o, VBy: IntoOwned<RAW=Notification<V>>+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<V>>
+for DematerializeOp<SS, VBy, Src>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<V>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(NotificationState{ sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)) }) });
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                let n = v.into_owned();
+                state.sub.if_not_done(|| match n {
+                    Notification::Next(v) => state.next.call(v),
+                    Notification::Error(e) => {
+                        let rearmed = e.clone();
+                        e.set_handled();
+                        state.end(Some(rearmed));
+                    },
+                    Notification::Complete => state.end(None)
+                });
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| state.end(e));
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<V>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    fn fmt(n: &Notification<i32>) -> String
+    {
+        match n {
+            Notification::Next(v) => format!("{}", v),
+            Notification::Error(_) => "E".to_owned(),
+            Notification::Complete => "C".to_owned()
+        }
+    }
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(0..3).materialize().subscribe(
+            |n: Notification<i32>| out.borrow_mut().push_str(&fmt(&n)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "012Cok");
+    }
+
+    #[test]
+    fn error()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        //the materialized error may be dropped without handling it
+        o.materialize().subscribe(
+            |n: Notification<i32>| out.borrow_mut().push_str(&fmt(&n)),
+            |e: Option<RxError>| out.borrow_mut().push_str(if e.is_some() { "err" } else { "ok" })
+        );
+
+        i.next(1);
+        i.error(RxError::simple(None, "error"));
+        assert_eq!(out.borrow().as_str(), "1Eok");
+    }
+
+    #[test]
+    fn round_trip()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.materialize().into_dyn().dematerialize().subscribe(
+            |v: i32| out.borrow_mut().push_str(&format!("{}", v)),
+            |e: Option<RxError>| out.borrow_mut().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" })
+        );
+
+        i.next(1);
+        i.next(2);
+        i.error(RxError::simple(None, "error"));
+        assert_eq!(out.borrow().as_str(), "12err");
+    }
+
+    #[test]
+    fn clone()
+    {
+        let n = Notification::<i32>::Error(RxError::simple(None, "error").set_handled());
+        drop(n.clone());
+        drop(n);
+    }
+
+    #[test]
+    fn dematerialize_refs()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, Notification<i32>>::new()).clones();
+
+        //`Ref` notifications are cloned on the way in, the re-raised error still has to be handled
+        o.dematerialize().subscribe(
+            |v: i32| out.borrow_mut().push_str(&format!("{}", v)),
+            |e: Option<RxError>| out.borrow_mut().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" })
+        );
+
+        i.next(Notification::Next(1));
+        i.next(Notification::Error(RxError::simple(None, "error").set_handled()));
+        assert_eq!(out.borrow().as_str(), "1err");
+    }
+
+    #[test]
+    fn dematerialize()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, Notification<i32>>::new()).clones();
+
+        let sub = o.dematerialize().subscribe(
+            |v: i32| out.borrow_mut().push_str(&format!("{}", v)),
+            |e: Option<RxError>| out.borrow_mut().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" })
+        );
+
+        i.next(Notification::Next(1));
+        i.next(Notification::Complete);
+        i.next(Notification::Next(2));
+
+        assert!(sub.is_done());
+        assert_eq!(out.borrow().as_str(), "1ok");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.materialize().dematerialize().subscribe(move |v| out.lock().unwrap().push(v), ());
+
+        ::std::thread::spawn(move || {
+            for v in 0..3 { i.next(v); }
+            i.complete();
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), vec![0, 1, 2]);
+    }
+}
@@ -5,6 +5,43 @@ mod take;
 mod skip;
 mod start;
 mod merge;
+mod concat;
+mod zip;
+mod combine_latest;
+mod with_latest_from;
+mod debounce;
+mod throttle_time;
+mod delay;
+mod timeout;
+mod sample;
+mod audit;
+mod buffer;
+mod buffer_count;
+mod window;
+mod window_count;
+mod group_by;
+mod distinct_until_changed;
+mod distinct;
+mod pairwise;
+mod take_while;
+mod skip_while;
+mod take_last;
+mod skip_last;
+mod first;
+mod last;
+mod catch_error;
+mod retry;
+mod backoff;
+mod repeat;
+mod tap;
+mod materialize;
+mod scan;
+mod reduce;
+mod flat_map;
+mod single_inner;
+mod switch_map;
+mod concat_map;
+mod exhaust_map;
 
 pub use self::map::*;
 pub use self::filter::*;
@@ -12,4 +49,40 @@ pub use self::until::*;
 pub use self::take::*;
 pub use self::skip::*;
 pub use self::start::*;
-pub use self::merge::*;
\ No newline at end of file
+pub use self::merge::*;
+pub use self::concat::*;
+pub use self::zip::*;
+pub use self::combine_latest::*;
+pub use self::with_latest_from::*;
+pub use self::debounce::*;
+pub use self::throttle_time::*;
+pub use self::delay::*;
+pub use self::timeout::*;
+pub use self::sample::*;
+pub use self::audit::*;
+pub use self::buffer::*;
+pub use self::buffer_count::*;
+pub use self::window::*;
+pub use self::window_count::*;
+pub use self::group_by::*;
+pub use self::distinct_until_changed::*;
+pub use self::distinct::*;
+pub use self::pairwise::*;
+pub use self::take_while::*;
+pub use self::skip_while::*;
+pub use self::take_last::*;
+pub use self::skip_last::*;
+pub use self::first::*;
+pub use self::last::*;
+pub use self::catch_error::*;
+pub use self::retry::*;
+pub use self::backoff::*;
+pub use self::repeat::*;
+pub use self::tap::*;
+pub use self::materialize::*;
+pub use self::scan::*;
+pub use self::reduce::*;
+pub use self::flat_map::*;
+pub use self::switch_map::*;
+pub use self::concat_map::*;
+pub use self::exhaust_map::*;
\ No newline at end of file
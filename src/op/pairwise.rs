@@ -0,0 +1,161 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::cell::UnsafeCell;
+
+pub struct PairwiseOp<SS, VBy, Src>
+{
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>)>
+}
+
+pub struct BufferWithPreviousOp<SS, VBy, Src>
+{
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>)>
+}
+
+pub trait ObsPairwiseOp<SS: YesNo, VBy: RefOrVal> : Sized
+{
+    fn pairwise(self) -> PairwiseOp<SS, VBy, Self> { PairwiseOp{ src: self, PhantomData } }
+    fn buffer_with_previous(self) -> BufferWithPreviousOp<SS, VBy, Self> { BufferWithPreviousOp{ src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+ObsPairwiseOp<SS, VBy> for Src where VBy::RAW: Clone {}
+
+pub trait DynObsPairwiseOp<'o, SS: YesNo, VBy: IntoOwned+'o>
+{
+    fn pairwise(self) -> DynObservable<'o, 'o, SS, Val<(VBy::RAW, VBy::RAW)>>;
+    fn buffer_with_previous(self) -> DynObservable<'o, 'o, SS, Val<(Option<VBy::RAW>, VBy::RAW)>>;
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o>
+DynObsPairwiseOp<'o, SS, VBy>
+for DynObservable<'o, 'o, SS, VBy>
+where VBy::RAW: Clone
+{
+    fn pairwise(self) -> DynObservable<'o, 'o, SS, Val<(VBy::RAW, VBy::RAW)>>
+    { PairwiseOp{ src: self.src, PhantomData }.into_dyn() }
+
+    fn buffer_with_previous(self) -> DynObservable<'o, 'o, SS, Val<(Option<VBy::RAW>, VBy::RAW)>>
+    { BufferWithPreviousOp{ src: self.src, PhantomData }.into_dyn() }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<(VBy::RAW, VBy::RAW)>>
+for PairwiseOp<SS, VBy, Src>
+where VBy::RAW: Clone
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<(VBy::RAW, VBy::RAW)>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let next = SSActNextWrap::new(next);
+        let sub = Unsub::new();
+        //no mutex here because access is protected by Unsub's internal lock
+        let prev = unsafe{ AnySendSync::new(UnsafeCell::new(None)) };
+
+        sub.clone().added_each(self.src.subscribe(forward_next(next, (sub, SSWrap::new(prev)), |next, (sub, prev), v: VBy| {
+            sub.if_not_done(|| {
+                let v = v.into_owned();
+                if let Some(prev) = unsafe{ &mut *prev.get() }.replace(v.clone()) {
+                    next.call((prev, v));
+                }
+            });
+        }, |s, _| s.stopped()), ec))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<(VBy::RAW, VBy::RAW)>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<(Option<VBy::RAW>, VBy::RAW)>>
+for BufferWithPreviousOp<SS, VBy, Src>
+where VBy::RAW: Clone
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<(Option<VBy::RAW>, VBy::RAW)>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let next = SSActNextWrap::new(next);
+        let sub = Unsub::new();
+        //no mutex here because access is protected by Unsub's internal lock
+        let prev = unsafe{ AnySendSync::new(UnsafeCell::new(None)) };
+
+        sub.clone().added_each(self.src.subscribe(forward_next(next, (sub, SSWrap::new(prev)), |next, (sub, prev), v: VBy| {
+            sub.if_not_done(|| {
+                let v = v.into_owned();
+                let prev = unsafe{ &mut *prev.get() }.replace(v.clone());
+                next.call((prev, v));
+            });
+        }, |s, _| s.stopped()), ec))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<(Option<VBy::RAW>, VBy::RAW)>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(1..5).pairwise().subscribe(
+            |(a, b)| out.borrow_mut().push_str(&format!("{}{},", a, b)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        assert_eq!(out.borrow().as_str(), "12,23,34,ok");
+    }
+
+    #[test]
+    fn refs()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, String>::new()).clones();
+
+        o.into_dyn().pairwise().subscribe(|(a, b): (String, String)| out.borrow_mut().push_str(&format!("{}{},", a, b)), ());
+
+        i.next("a".to_owned());
+        assert_eq!(out.borrow().as_str(), "");
+
+        i.next("b".to_owned());
+        i.next("c".to_owned());
+        assert_eq!(out.borrow().as_str(), "ab,bc,");
+    }
+
+    #[test]
+    fn buffer_with_previous()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(1..4).buffer_with_previous().subscribe(
+            |(a, b): (Option<i32>, i32)| out.borrow_mut().push_str(&format!("{:?}{},", a, b)), ()
+        );
+
+        assert_eq!(out.borrow().as_str(), "None1,Some(1)2,Some(2)3,");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(0)).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.pairwise().subscribe(move |(a, b): (i32, i32)| *out.lock().unwrap() += b - a, ());
+
+        ::std::thread::spawn(move || {
+            for v in vec![1, 4, 9, 16] { i.next(v); }
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), 15);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+
+pub struct ReduceOp<SS, VBy, Src, F, Acc>
+{
+    f: Arc<F>,
+    seed: Acc,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>)>
+}
+
+pub trait ObsReduceOp<'o, SS: YesNo, VBy: RefOrVal, Acc, F: ActFold<SS, VBy, Acc>+'o> : Sized
+{
+    fn reduce(self, seed: Acc, f: F) -> ReduceOp<SS, VBy, Self, F, Acc> { ReduceOp{ f: Arc::new(f), seed, src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Acc: Clone+'o, Src: Observable<'o, SS, VBy>+'o, F: ActFold<SS, VBy, Acc>+'o>
+ObsReduceOp<'o, SS, VBy, Acc, F> for Src {}
+
+pub trait DynObsReduceOp<'o, SS: YesNo, VBy: RefOrVal+'o, Acc: Clone+'o, F: ActFold<SS, VBy, Acc>+'o>
+{
+    fn reduce(self, seed: Acc, f: F) -> DynObservable<'o, 'o, SS, Val<Acc>>;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Acc: Clone+'o, F: ActFold<SS, VBy, Acc>+'o>
+DynObsReduceOp<'o, SS, VBy, Acc, F>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn reduce(self, seed: Acc, f: F) -> DynObservable<'o, 'o, SS, Val<Acc>>
+    { ReduceOp{ f: Arc::new(f), seed, src: self.src, PhantomData }.into_dyn() }
+}
+
+impl<'s, 'o, SS:YesNo, VBy: RefOrVal+'o, Acc: Clone+'o, Src: Observable<'o, SS, VBy>+'s, F: ActFold<SS, VBy, Acc>+'o>
+Observable<'o, SS, Val<Acc>>
+for ReduceOp<SS, VBy, Src, F, Acc>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<Acc>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let f = fold_sendsync(self.f.clone());
+        let sub = Unsub::new();
+        //no mutex here because access is protected by Unsub's internal lock
+        let state = Arc::new(unsafe{ AnySendSync::new((next, UnsafeCell::new(Some(self.seed.clone())), UnsafeCell::new(Some(ec)))) });
+
+        sub.clone().added_each(self.src.subscribe(
+            forward_next((), (sub.clone(), f, SSWrap::new(state.clone())), |(), (sub, f, state), v: VBy| {
+                sub.if_not_done(|| {
+                    let acc = unsafe{ &mut *state.1.get() };
+                    let v = f.call(acc.take().unwrap(), v.into_v());
+                    acc.replace(v);
+                });
+            }, |(), (sub, _, state)| state.0.stopped() || sub.is_done()),
+
+            forward_ec((sub, SSWrap::new(state)), |(sub, state), e: Option<RxError>| {
+                if let Some(ec) = unsafe{ &mut *state.2.get() }.take() {
+                    let acc = unsafe{ &mut *state.1.get() }.take();
+                    if let (None, Some(acc)) = (&e, acc) {
+                        if !state.0.stopped() { state.0.call(acc); }
+                    }
+                    sub.unsub();
+                    ec.call_once(e);
+                }
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<Acc>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.reduce(0, |acc, v:&_| acc + *v).subscribe(|v:i32| out.borrow_mut().push_str(&format!("{},", v)), |_e| out.borrow_mut().push_str("ok"));
+
+        for v in 1..5 {
+            i.next(v);
+        }
+        assert_eq!(out.borrow().as_str(), "");
+
+        i.complete();
+        assert_eq!(out.borrow().as_str(), "10,ok");
+    }
+
+    #[test]
+    fn error()
+    {
+        let out = RefCell::new(String::new());
+        let o = Rc::new(iter_once(vec![1, 2, 3].into_iter()));
+
+        o.clone().reduce(0, |acc, v| acc + v).subscribe(|_v| {}, ());
+        o.reduce(0, |acc, v| acc + v).subscribe(|v:i32| out.borrow_mut().push_str(&format!("{},", v)), |e: Option<RxError>| {
+            assert!(e.is_some());
+            e.unwrap().set_handled();
+            out.borrow_mut().push_str("err");
+        });
+
+        assert_eq!(out.borrow().as_str(), "err");
+    }
+
+    #[test]
+    fn iter()
+    {
+        let n = Cell::new(0);
+        iter_clone(vec![1, 2, 3, 4].into_iter()).into_dyn().reduce(1, |acc, v| acc * v).subscribe(|v| { n.replace(v); }, |_e| { n.replace(n.get() + 100); });
+
+        assert_eq!(n.get(), 124);
+
+        let n = Cell::new(0);
+        Of::<NO, i32>::empty().reduce(123, |acc, v:&_| acc + v).subscribe(|v| { n.replace(v); }, ());
+
+        assert_eq!(n.get(), 123);
+    }
+
+    #[test]
+    fn merge()
+    {
+        let n = Cell::new(0);
+        Of::value(1).merge(Of::value(2)).reduce(10, |acc, v:&_| acc + v).subscribe(|v| { n.replace(v); }, ());
+
+        assert_eq!(n.get(), 13);
+    }
+
+    #[test]
+    fn timer()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1, out2) = Arc::new(Mutex::new(String::new())).clones();
+
+        Timer::new(Duration::from_millis(10), sch.clone()).take(5).reduce(0, |acc, v| acc + v).subscribe(
+            move |v: usize| out.lock().unwrap().push_str(&format!("{}", v)),
+            move |_e| out1.lock().unwrap().push_str("ok")
+        );
+
+        ::std::thread::sleep(Duration::from_millis(500));
+        assert_eq!(out2.lock().unwrap().as_str(), "10ok");
+    }
+}
@@ -0,0 +1,291 @@
+use crate::*;
+use super::retry::{Resub, ResubTrigger, ResubCount, ResubWhen, ResubState, resubscribe};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::Cell;
+
+pub struct RepeatOp<'o, SS:YesNo, VBy: RefOrVal>
+{
+    count: Option<usize>,
+    src: DynObservable<'o, 'o, SS, VBy>,
+}
+
+pub struct RepeatUntilOp<'o, SS:YesNo, VBy: RefOrVal, F>
+{
+    f: Arc<F>,
+    src: DynObservable<'o, 'o, SS, VBy>,
+}
+
+pub struct RepeatWhenOp<'o, SS:YesNo, VBy: RefOrVal, F, NBy>
+{
+    f: Arc<F>,
+    src: DynObservable<'o, 'o, SS, VBy>,
+    PhantomData: PhantomData<AnySendSync<NBy>>
+}
+
+pub trait ObsRepeatOp<'o, SS: YesNo, VBy: RefOrVal+'o> : Sized
+{
+    //subscribes `count` times in total
+    fn repeat(self, count: usize) -> RepeatOp<'o, SS, VBy> where Self: Observable<'o, SS, VBy>+'o
+    { RepeatOp{ count: Some(count), src: self.into_dyn() } }
+
+    fn repeat_forever(self) -> RepeatOp<'o, SS, VBy> where Self: Observable<'o, SS, VBy>+'o
+    { RepeatOp{ count: None, src: self.into_dyn() } }
+
+    //asked after each completion, resubscribes until `f` returns true
+    fn repeat_until<F: Act<SS, (), bool>+'o>(self, f: F) -> RepeatUntilOp<'o, SS, VBy, F> where Self: Observable<'o, SS, VBy>+'o
+    { RepeatUntilOp{ f: Arc::new(f), src: self.into_dyn() } }
+
+    //each emission of the notifier resubscribes, its completion or error ends the sequence
+    fn repeat_when<NBy: RefOrVal+'o, F: Act<SS, Val<DynObservable<'o, 'o, SS, Ref<()>>>, DynObservable<'o, 'o, SS, NBy>>+'o>(self, f: F) -> RepeatWhenOp<'o, SS, VBy, F, NBy>
+        where Self: Observable<'o, SS, VBy>+'o
+    { RepeatWhenOp{ f: Arc::new(f), src: self.into_dyn(), PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>+'o>
+ObsRepeatOp<'o, SS, VBy> for Src {}
+
+pub trait DynObsRepeatOp<'o, SS: YesNo, VBy: RefOrVal+'o>
+{
+    fn repeat(self, count: usize) -> Self;
+    fn repeat_forever(self) -> Self;
+    fn repeat_until<F: Act<SS, (), bool>+'o>(self, f: F) -> Self;
+    fn repeat_when<NBy: RefOrVal+'o, F: Act<SS, Val<DynObservable<'o, 'o, SS, Ref<()>>>, DynObservable<'o, 'o, SS, NBy>>+'o>(self, f: F) -> Self;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o>
+DynObsRepeatOp<'o, SS, VBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn repeat(self, count: usize) -> Self
+    { RepeatOp{ count: Some(count), src: self }.into_dyn() }
+
+    fn repeat_forever(self) -> Self
+    { RepeatOp{ count: None, src: self }.into_dyn() }
+
+    fn repeat_until<F: Act<SS, (), bool>+'o>(self, f: F) -> Self
+    { RepeatUntilOp{ f: Arc::new(f), src: self }.into_dyn() }
+
+    fn repeat_when<NBy: RefOrVal+'o, F: Act<SS, Val<DynObservable<'o, 'o, SS, Ref<()>>>, DynObservable<'o, 'o, SS, NBy>>+'o>(self, f: F) -> Self
+    { RepeatWhenOp{ f: Arc::new(f), src: self, PhantomData }.into_dyn() }
+}
+
+struct ResubUntil<SS, F>
+{
+    f: Arc<F>,
+    PhantomData: PhantomData<SS>
+}
+
+impl<SS:YesNo, F: Act<SS, (), bool>> ResubTrigger for ResubUntil<SS, F>
+{
+    fn trigger(&self, e: Option<RxError>) -> Resub
+    {
+        match e {
+            None if !self.f.call(()) => Resub::Now,
+            e => Resub::End(e)
+        }
+    }
+}
+
+impl<'o, SS:YesNo> ResubTrigger for ResubWhen<'o, SS, ()>
+{
+    fn trigger(&self, e: Option<RxError>) -> Resub
+    {
+        match e {
+            None => {
+                self.subject.next(());
+                Resub::Wait
+            },
+            e => Resub::End(e)
+        }
+    }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o>
+Observable<'o, SS, VBy>
+for RepeatOp<'o, SS, VBy>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, VBy>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        if self.count == Some(0) {
+            ec.call_once(None);
+            return Unsub::done();
+        }
+
+        let sub = Unsub::new();
+        let remaining = self.count.map(|n| n - 1);
+        let state = ResubState::new(sub.clone(), next, ec, self.src.clone(), ResubCount{ on_error: false, remaining: Cell::new(remaining) });
+
+        sub.if_not_done(|| resubscribe(&state));
+        sub
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, VBy>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, F: Act<SS, (), bool>+'o>
+Observable<'o, SS, VBy>
+for RepeatUntilOp<'o, SS, VBy, F>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, VBy>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = ResubState::new(sub.clone(), next, ec, self.src.clone(), ResubUntil{ f: self.f.clone(), PhantomData });
+
+        sub.if_not_done(|| resubscribe(&state));
+        sub
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, VBy>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, NBy: RefOrVal+'o, F: Act<SS, Val<DynObservable<'o, 'o, SS, Ref<()>>>, DynObservable<'o, 'o, SS, NBy>>+'o>
+Observable<'o, SS, VBy>
+for RepeatWhenOp<'o, SS, VBy, F, NBy>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, VBy>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let subject = Arc::new(Subject::new());
+        let notifier = self.f.call(subject.clone().into_dyn());
+        let state = ResubState::new(sub.clone(), next, ec, self.src.clone(), ResubWhen{ subject });
+
+        sub.add(notifier.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, _v: NBy| {
+                state.sub.if_not_done(|| resubscribe(&*state));
+            }, |(), state| state.sub.is_done()),
+
+            forward_ec(SSWrap::new(state.clone()), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| state.end(e));
+            })
+        ));
+
+        sub.if_not_done(|| resubscribe(&state));
+        sub
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, VBy>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::Cell;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(0..3).repeat(3).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "012012012ok");
+
+        let out = RefCell::new(String::new());
+        iter_clone(0..3).into_dyn().repeat(0).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "ok");
+    }
+
+    #[test]
+    fn trampoline()
+    {
+        let n = Cell::new(0);
+        let done = Cell::new(false);
+
+        Of::value(1).repeat(100_000).subscribe(|v: &i32| { n.replace(n.get() + *v); }, |_e| { done.replace(true); });
+
+        assert_eq!(n.get(), 100_000);
+        assert!(done.get());
+    }
+
+    #[test]
+    fn forever()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(0..2).repeat_forever().take(5).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "01010ok");
+    }
+
+    #[test]
+    fn until()
+    {
+        let out = RefCell::new(String::new());
+        let n = Cell::new(0);
+
+        iter_clone(0..2).repeat_until(|| { n.replace(n.get() + 1); n.get() == 3 }).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "010101ok");
+        assert_eq!(n.get(), 3);
+
+        let out = RefCell::new(String::new());
+        Of::value(1).into_dyn().repeat_until(|| true).subscribe(
+            |v: &i32| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "1ok");
+    }
+
+    #[test]
+    fn error()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.repeat_forever().subscribe(
+            |v: &i32| out.borrow_mut().push_str(&format!("{}", v)),
+            |e: Option<RxError>| out.borrow_mut().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" })
+        );
+
+        i.next(1);
+        i.error(RxError::simple(None, "error"));
+        assert_eq!(out.borrow().as_str(), "1err");
+    }
+
+    #[test]
+    fn when()
+    {
+        let (out, out1) = Rc::new(RefCell::new(String::new())).clones();
+
+        iter_clone(0..2).repeat_when(|c: DynObservable<'static, 'static, NO, Ref<()>>| c.take(2)).subscribe(
+            move |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| {}
+        );
+        assert_eq!(out1.borrow().as_str(), "0101");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.repeat(2).subscribe(move |v:&_| out.lock().unwrap().push(*v), ());
+
+        ::std::thread::spawn(move || {
+            for v in 0..3 { i.next(v); }
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), vec![0, 1, 2]);
+    }
+}
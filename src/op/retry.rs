@@ -0,0 +1,364 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::Cell;
+use std::cell::UnsafeCell;
+
+pub struct RetryOp<'o, SS:YesNo, VBy: RefOrVal>
+{
+    count: usize,
+    src: DynObservable<'o, 'o, SS, VBy>,
+}
+
+pub struct RetryWhenOp<'o, SS:YesNo, VBy: RefOrVal, F, NBy>
+{
+    f: Arc<F>,
+    src: DynObservable<'o, 'o, SS, VBy>,
+    PhantomData: PhantomData<AnySendSync<NBy>>
+}
+
+pub trait ObsRetryOp<'o, SS: YesNo, VBy: RefOrVal+'o> : Sized
+{
+    //resubscribes at most `count` times, the last error is forwarded
+    fn retry(self, count: usize) -> RetryOp<'o, SS, VBy> where Self: Observable<'o, SS, VBy>+'o
+    { RetryOp{ count, src: self.into_dyn() } }
+
+    //each emission of the notifier resubscribes, its completion or error ends the sequence
+    fn retry_when<NBy: RefOrVal+'o, F: Act<SS, Val<DynObservable<'o, 'o, SS, Ref<RxError>>>, DynObservable<'o, 'o, SS, NBy>>+'o>(self, f: F) -> RetryWhenOp<'o, SS, VBy, F, NBy>
+        where Self: Observable<'o, SS, VBy>+'o
+    { RetryWhenOp{ f: Arc::new(f), src: self.into_dyn(), PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>+'o>
+ObsRetryOp<'o, SS, VBy> for Src {}
+
+pub trait DynObsRetryOp<'o, SS: YesNo, VBy: RefOrVal+'o>
+{
+    fn retry(self, count: usize) -> Self;
+    fn retry_when<NBy: RefOrVal+'o, F: Act<SS, Val<DynObservable<'o, 'o, SS, Ref<RxError>>>, DynObservable<'o, 'o, SS, NBy>>+'o>(self, f: F) -> Self;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o>
+DynObsRetryOp<'o, SS, VBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn retry(self, count: usize) -> Self
+    { RetryOp{ count, src: self }.into_dyn() }
+
+    fn retry_when<NBy: RefOrVal+'o, F: Act<SS, Val<DynObservable<'o, 'o, SS, Ref<RxError>>>, DynObservable<'o, 'o, SS, NBy>>+'o>(self, f: F) -> Self
+    { RetryWhenOp{ f: Arc::new(f), src: self, PhantomData }.into_dyn() }
+}
+
+pub(crate) enum Resub
+{
+    Now,
+    Wait,
+    End(Option<RxError>)
+}
+
+pub(crate) trait ResubTrigger
+{
+    fn trigger(&self, e: Option<RxError>) -> Resub;
+}
+
+//`remaining: None` resubscribes forever
+pub(crate) struct ResubCount
+{
+    pub(crate) on_error: bool,
+    pub(crate) remaining: Cell<Option<usize>>,
+}
+
+impl ResubTrigger for ResubCount
+{
+    fn trigger(&self, e: Option<RxError>) -> Resub
+    {
+        if e.is_some() != self.on_error { return Resub::End(e); }
+
+        match self.remaining.get() {
+            Some(0) => Resub::End(e),
+            remaining => {
+                self.remaining.replace(remaining.map(|n| n - 1));
+                if let Some(e) = e { e.set_handled(); }
+                Resub::Now
+            }
+        }
+    }
+}
+
+//feeds the notifier's input, which resubscribes through its own subscription
+pub(crate) struct ResubWhen<'o, SS:YesNo, T>
+{
+    pub(crate) subject: Arc<Subject<'o, SS, T>>,
+}
+
+impl<'o, SS:YesNo> ResubTrigger for ResubWhen<'o, SS, RxError>
+{
+    fn trigger(&self, e: Option<RxError>) -> Resub
+    {
+        match e {
+            Some(e) => {
+                self.subject.next(e.set_handled());
+                Resub::Wait
+            },
+            None => Resub::End(None)
+        }
+    }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+pub(crate) struct ResubState<'o, SS:YesNo, VBy: RefOrVal, N, EC, T>
+{
+    pub(crate) sub: Unsub<'o, SS>,
+    pub(crate) next: N,
+    pub(crate) ec: UnsafeCell<Option<EC>>,
+    pub(crate) src: DynObservable<'o, 'o, SS, VBy>,
+    pub(crate) trigger: T,
+    pub(crate) attempt: UnsafeCell<Unsub<'o, SS>>,
+    pub(crate) looping: Cell<bool>,
+    pub(crate) pending: Cell<bool>,
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, N: ActNext<'o, SS, VBy>, EC: ActEc<'o, SS>, T: ResubTrigger+'o> ResubState<'o, SS, VBy, N, EC, T>
+{
+    pub(crate) fn new(sub: Unsub<'o, SS>, next: N, ec: EC, src: DynObservable<'o, 'o, SS, VBy>, trigger: T) -> Arc<AnySendSync<Self>>
+    {
+        let state = Arc::new(unsafe{ AnySendSync::new(ResubState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), src, trigger,
+            attempt: UnsafeCell::new(Unsub::done()), looping: Cell::new(false), pending: Cell::new(false)
+        }) });
+
+        sub.add(Unsub::with(forward_act_once(SSWrap::new(state.clone()), |state, ()| {
+            unsafe{ &*state.attempt.get() }.unsub();
+        })));
+
+        state
+    }
+
+    pub(crate) fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+}
+
+//a source that ends synchronously asks for the next attempt while the current one is still subscribing:
+//it's only flagged as `pending` and picked up by the loop, so the stack doesn't grow with each attempt
+pub(crate) fn resubscribe<'o, SS:YesNo, VBy: RefOrVal+'o, N: ActNext<'o, SS, VBy>, EC: ActEc<'o, SS>, T: ResubTrigger+'o>
+(state: &Arc<AnySendSync<ResubState<'o, SS, VBy, N, EC, T>>>)
+{
+    if state.looping.get() {
+        state.pending.replace(true);
+        return;
+    }
+
+    state.looping.replace(true);
+    loop {
+        state.pending.replace(false);
+
+        let attempt = Unsub::new();
+        unsafe{ ::std::mem::replace(&mut *state.attempt.get(), attempt.clone()) }.unsub();
+
+        attempt.add(state.src.subscribe(
+            forward_next((), (SSWrap::new(state.clone()), attempt.clone()), |(), (state, attempt), v: VBy| {
+                state.sub.if_not_done(|| if !attempt.is_done() { state.next.call(v.into_v()); });
+            }, |(), (state, attempt)| attempt.is_done() || state.sub.is_done() || state.next.stopped()),
+
+            forward_ec((SSWrap::new(state.clone()), attempt.clone()), |(state, attempt), e: Option<RxError>| {
+                state.sub.if_not_done(|| {
+                    if attempt.is_done() { return; }
+                    attempt.unsub();
+
+                    match state.trigger.trigger(e) {
+                        Resub::Now => resubscribe(&*state),
+                        Resub::Wait => {},
+                        Resub::End(e) => state.end(e)
+                    }
+                });
+            })
+        ));
+
+        if !state.pending.get() || state.sub.is_done() || state.next.stopped() { break; }
+    }
+    state.looping.replace(false);
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o>
+Observable<'o, SS, VBy>
+for RetryOp<'o, SS, VBy>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, VBy>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = ResubState::new(sub.clone(), next, ec, self.src.clone(), ResubCount{ on_error: true, remaining: Cell::new(Some(self.count)) });
+
+        sub.if_not_done(|| resubscribe(&state));
+        sub
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, VBy>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, NBy: RefOrVal+'o, F: Act<SS, Val<DynObservable<'o, 'o, SS, Ref<RxError>>>, DynObservable<'o, 'o, SS, NBy>>+'o>
+Observable<'o, SS, VBy>
+for RetryWhenOp<'o, SS, VBy, F, NBy>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, VBy>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let subject = Arc::new(Subject::new());
+        let notifier = self.f.call(subject.clone().into_dyn());
+        let state = ResubState::new(sub.clone(), next, ec, self.src.clone(), ResubWhen{ subject });
+
+        sub.add(notifier.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, _v: NBy| {
+                state.sub.if_not_done(|| resubscribe(&*state));
+            }, |(), state| state.sub.is_done()),
+
+            forward_ec(SSWrap::new(state.clone()), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| state.end(e));
+            })
+        ));
+
+        sub.if_not_done(|| resubscribe(&state));
+        sub
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, VBy>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::Cell;
+    use std::cell::RefCell;
+    use std::marker::PhantomData;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    //errors on the first `fails` subscriptions, then emits the number of the attempt
+    struct Flaky<SS:YesNo>{ fails: usize, attempts: Arc<Mutex<usize>>, PhantomData: PhantomData<SS> }
+
+    impl<SS:YesNo> Flaky<SS>
+    {
+        fn new(fails: usize) -> Self { Flaky{ fails, attempts: Arc::new(Mutex::new(0)), PhantomData } }
+    }
+
+    impl<'o, SS:YesNo> Observable<'o, SS, Val<usize>> for Flaky<SS>
+    {
+        fn subscribe(&self, next: impl ActNext<'o, SS, Val<usize>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+        {
+            let mut attempts = self.attempts.lock().unwrap();
+            *attempts += 1;
+            if *attempts <= self.fails {
+                ec.call_once(Some(RxError::simple(None, "flaky")));
+            } else {
+                next.call(*attempts);
+                ec.call_once(None);
+            }
+            Unsub::done()
+        }
+
+        fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<usize>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+        { self.subscribe(next, ec) }
+    }
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+
+        Flaky::<NO>::new(2).retry(2).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |e: Option<RxError>| out.borrow_mut().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" })
+        );
+        assert_eq!(out.borrow().as_str(), "3ok");
+
+        let out = RefCell::new(String::new());
+        Flaky::<NO>::new(3).into_dyn().retry(2).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |e: Option<RxError>| out.borrow_mut().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" })
+        );
+        assert_eq!(out.borrow().as_str(), "err");
+    }
+
+    #[test]
+    fn trampoline()
+    {
+        let n = Cell::new(0);
+        Flaky::<NO>::new(100_000).retry(100_000).subscribe(|v| { n.replace(v); }, ());
+
+        assert_eq!(n.get(), 100_001);
+    }
+
+    #[test]
+    fn values_before_error()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.retry(1).subscribe(
+            |v: &i32| out.borrow_mut().push_str(&format!("{}", v)),
+            |e: Option<RxError>| out.borrow_mut().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" })
+        );
+
+        i.next(1);
+        i.error(RxError::simple(None, "error"));
+        assert_eq!(out.borrow().as_str(), "1err");
+    }
+
+    #[test]
+    fn when()
+    {
+        let (out, out1) = Rc::new(RefCell::new(String::new())).clones();
+        let (errors, errors1) = Rc::new(Cell::new(0)).clones();
+
+        Flaky::<NO>::new(2).retry_when(move |e: DynObservable<'static, 'static, NO, Ref<RxError>>| {
+            let errors = errors.clone();
+            e.map(move |_: &_| { errors.replace(errors.get() + 1); })
+        }).subscribe(
+            move |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| {}
+        );
+        assert_eq!(out1.borrow().as_str(), "3");
+        assert_eq!(errors1.get(), 2);
+
+        let (out, out1) = Rc::new(RefCell::new(String::new())).clones();
+        Flaky::<NO>::new(5).retry_when(|e: DynObservable<'static, 'static, NO, Ref<RxError>>| e.take(1)).subscribe(
+            |v| panic!("shouldn't emit {}", v),
+            move |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out1.borrow().as_str(), "ok");
+    }
+
+    #[test]
+    fn unsub()
+    {
+        let n = Cell::new(0);
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        let sub = o.retry(5).subscribe(|_: &i32| { n.replace(n.get() + 1); }, ());
+        i.next(1);
+        sub.unsub();
+        i.next(1);
+
+        assert_eq!(n.get(), 1);
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let src = Flaky::<YES>::new(3);
+
+        ::std::thread::spawn(move || {
+            src.retry(3).subscribe(move |v| out.lock().unwrap().push(v), ());
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), vec![4]);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+use std::time::Duration;
+
+pub struct SampleOp<'o, SS:YesNo, VBy, Src, SVBy: RefOrVal>
+{
+    src: Src,
+    sig: DynObservable<'o, 'o, SS, SVBy>,
+    PhantomData: PhantomData<AnySendSync<VBy>>
+}
+
+pub trait ObsSampleOp<'o, SS:YesNo, VBy: RefOrVal, SVBy: RefOrVal> : Sized
+{
+    fn sample(self, sig: impl Observable<'o, SS, SVBy>+'o) -> SampleOp<'o, SS, VBy, Self, SVBy>
+    {
+        SampleOp{ src: self, sig: sig.into_dyn(), PhantomData }
+    }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, SVBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>>
+ObsSampleOp<'o, SS, VBy, SVBy>
+for Src {}
+
+pub trait DynObsSampleOp<'o, SS:YesNo, VBy: IntoOwned, SVBy: RefOrVal> : Sized
+{
+    fn sample(self, sig: impl Observable<'o, SS, SVBy>+'o) -> DynObservable<'o, 'o, SS, Val<VBy::RAW>>;
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, SVBy: RefOrVal+'o>
+DynObsSampleOp<'o, SS, VBy, SVBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn sample(self, sig: impl Observable<'o, SS, SVBy>+'o) -> DynObservable<'o, 'o, SS, Val<VBy::RAW>>
+    {
+        SampleOp{ src: self.src, sig: sig.into_dyn(), PhantomData }.into_dyn()
+    }
+}
+
+pub trait ObsSampleTimeOp<SS:YesNo, VBy: RefOrVal, Sch: SchedulerPeriodic<SS>> : Sized
+{
+    fn sample_time(self, period: Duration, scheduler: Sch) -> SampleOp<'static, SS, VBy, Self, Val<usize>>;
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Src: Observable<'static, SS, VBy>, Sch: SchedulerPeriodic<SS>+'static>
+ObsSampleTimeOp<SS, VBy, Sch>
+for Src
+{
+    fn sample_time(self, period: Duration, scheduler: Sch) -> SampleOp<'static, SS, VBy, Self, Val<usize>>
+    {
+        self.sample(Timer::new(period, scheduler))
+    }
+}
+
+pub trait DynObsSampleTimeOp<SS:YesNo, VBy: IntoOwned, Sch: SchedulerPeriodic<SS>> : Sized
+{
+    fn sample_time(self, period: Duration, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<VBy::RAW>>;
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Sch: SchedulerPeriodic<SS>+'static>
+DynObsSampleTimeOp<SS, VBy, Sch>
+for DynObservable<'static, 'static, SS, VBy>
+{
+    fn sample_time(self, period: Duration, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<VBy::RAW>>
+    {
+        SampleOp{ src: self.src, sig: Timer::new(period, scheduler).into_dyn(), PhantomData }.into_dyn()
+    }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct SampleState<'o, SS:YesNo, N, EC, V>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    latest: UnsafeCell<Option<V>>,
+}
+
+impl<'o, SS:YesNo, N, EC: ActEc<'o, SS>, V> SampleState<'o, SS, N, EC, V>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, SVBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<VBy::RAW>>
+for SampleOp<'o, SS, VBy, Src, SVBy>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<VBy::RAW>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(SampleState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), latest: UnsafeCell::new(None)
+        }) });
+
+        sub.add(self.sig.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, _v: SVBy| {
+                state.sub.if_not_done(|| {
+                    if let Some(v) = unsafe{ &mut *state.latest.get() }.take() {
+                        if !state.next.stopped() { state.next.call(v); }
+                    }
+                });
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state.clone()), |state, e: Option<RxError>| {
+                if e.is_some() { state.sub.if_not_done(|| state.end(e)); }
+            })
+        ));
+
+        if sub.is_done() { return sub; }
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| unsafe{ *state.latest.get() = Some(v.into_owned()); });
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| state.end(e));
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<VBy::RAW>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (s, sig) = Rc::new(Subject::<NO, ()>::new()).clones();
+
+        o.sample(sig).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        s.next(());
+        i.next(1);
+        i.next(2);
+        s.next(());
+        s.next(());
+        i.next(3);
+        s.complete();
+        assert_eq!(out.borrow().as_str(), "2");
+
+        i.complete();
+        assert_eq!(out.borrow().as_str(), "2ok");
+    }
+
+    #[test]
+    fn sample_time()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.into_dyn().sample_time(Duration::from_millis(50), sch.clone()).subscribe(
+            move |v| out.lock().unwrap().push_str(&format!("{}", v)), ()
+        );
+
+        i.next(1);
+        i.next(2);
+        ::std::thread::sleep(Duration::from_millis(120));
+        assert_eq!(out1.lock().unwrap().as_str(), "2");
+
+        i.next(3);
+        ::std::thread::sleep(Duration::from_millis(120));
+        assert_eq!(out1.lock().unwrap().as_str(), "23");
+    }
+}
@@ -0,0 +1,128 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+
+pub struct ScanOp<SS, VBy, Src, F, Acc>
+{
+    f: Arc<F>,
+    seed: Acc,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>)>
+}
+
+pub trait ObsScanOp<'o, SS: YesNo, VBy: RefOrVal, Acc, F: ActFold<SS, VBy, Acc>+'o> : Sized
+{
+    fn scan(self, seed: Acc, f: F) -> ScanOp<SS, VBy, Self, F, Acc> { ScanOp{ f: Arc::new(f), seed, src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Acc: Clone+'o, Src: Observable<'o, SS, VBy>+'o, F: ActFold<SS, VBy, Acc>+'o>
+ObsScanOp<'o, SS, VBy, Acc, F> for Src {}
+
+pub trait DynObsScanOp<'o, SS: YesNo, VBy: RefOrVal+'o, Acc: Clone+'o, F: ActFold<SS, VBy, Acc>+'o>
+{
+    fn scan(self, seed: Acc, f: F) -> DynObservable<'o, 'o, SS, Val<Acc>>;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Acc: Clone+'o, F: ActFold<SS, VBy, Acc>+'o>
+DynObsScanOp<'o, SS, VBy, Acc, F>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn scan(self, seed: Acc, f: F) -> DynObservable<'o, 'o, SS, Val<Acc>>
+    { ScanOp{ f: Arc::new(f), seed, src: self.src, PhantomData }.into_dyn() }
+}
+
+impl<'s, 'o, SS:YesNo, VBy: RefOrVal+'o, Acc: Clone+'o, Src: Observable<'o, SS, VBy>+'s, F: ActFold<SS, VBy, Acc>+'o>
+Observable<'o, SS, Val<Acc>>
+for ScanOp<SS, VBy, Src, F, Acc>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<Acc>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let next = SSActNextWrap::new(next);
+        let f = fold_sendsync(self.f.clone());
+        let sub = Unsub::new();
+        //no mutex here because access is protected by Unsub's internal lock
+        let acc = unsafe{ AnySendSync::new(UnsafeCell::new(Some(self.seed.clone()))) };
+
+        sub.clone().added_each(self.src.subscribe(forward_next(next, (sub, f, SSWrap::new(acc)), |next, (sub, f, acc), v: VBy| {
+            sub.if_not_done(|| {
+                let acc = unsafe{ &mut *acc.get() };
+                let v = f.call(acc.take().unwrap(), v.into_v());
+                acc.replace(v.clone());
+                next.call(v);
+            });
+        }, |s, _| s.stopped()), ec))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<Acc>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::atomic::*;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.scan(0, |acc, v:&_| acc + *v).subscribe(|v:i32| out.borrow_mut().push_str(&format!("{},", v)), |_e| out.borrow_mut().push_str("ok"));
+
+        for v in 1..5 {
+            i.next(v);
+        }
+        i.complete();
+
+        assert_eq!(out.borrow().as_str(), "1,3,6,10,ok");
+    }
+
+    #[test]
+    fn val()
+    {
+        let n = Cell::new(0);
+        let (o, o1) = Rc::new(iter_clone(vec![1, 2, 3].into_iter())).clones();
+
+        o.scan(String::new(), |acc: String, v| format!("{}{}", acc, v)).subscribe(|v:String| { n.replace(v.len()); }, ());
+        assert_eq!(n.get(), 3);
+
+        o1.scan(10, |acc, v| acc * v).subscribe(|v| { n.replace(v); }, ());
+        assert_eq!(n.get(), 60);
+    }
+
+    #[test]
+    fn resubscribe_from_seed()
+    {
+        let n = Cell::new(0);
+        let o = Of::value(123).into_dyn().scan(1, |acc, v:&_| acc + v);
+
+        o.subscribe(|v| { n.replace(n.get() + v); }, ());
+        o.subscribe(|v| { n.replace(n.get() + v); }, ());
+
+        assert_eq!(n.get(), 248);
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (n, n1) = Arc::new(AtomicI32::new(0)).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.scan(0, |acc, v:&_| acc + v).subscribe(move |v| { n.store(v, Ordering::SeqCst); }, ());
+
+        ::std::thread::spawn(move ||{
+            for v in 0..10 { i.next(v); }
+        }).join().ok();
+
+        assert_eq!(n1.load(Ordering::SeqCst), 45);
+    }
+}
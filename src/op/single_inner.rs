@@ -0,0 +1,92 @@
+use crate::*;
+use std::sync::Arc;
+use std::cell::Cell;
+use std::cell::UnsafeCell;
+
+//shared by `switch_map` & `exhaust_map`: at most one inner is active at a time
+#[derive(Copy, Clone)]
+pub(crate) enum OnBusy
+{
+    //unsubscribe the active inner, subscribe the new one
+    Replace,
+    //ignore values while an inner is active
+    Drop,
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct SingleInnerState<'o, SS:YesNo, N, EC>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    inner: UnsafeCell<Unsub<'o, SS>>,
+    src_done: Cell<bool>,
+}
+
+impl<'o, SS:YesNo, N, EC: ActEc<'o, SS>> SingleInnerState<'o, SS, N, EC>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+}
+
+fn subscribe_inner<'o, SS:YesNo, By: RefOrVal+'o, N: ActNext<'o, SS, By>, EC: ActEc<'o, SS>, O: Observable<'o, SS, By>+'o>
+(state: &Arc<AnySendSync<SingleInnerState<'o, SS, N, EC>>>, o: O)
+{
+    let inner = Unsub::new();
+    unsafe{ ::std::mem::replace(&mut *state.inner.get(), inner.clone()) }.unsub();
+
+    //an old inner may still emit from another thread: `inner` is checked under `sub`'s lock before forwarding
+    inner.add(o.subscribe(
+        forward_next((), (SSWrap::new(state.clone()), inner.clone()), |(), (state, inner), v: By| {
+            state.sub.if_not_done(|| if !inner.is_done() { state.next.call(v.into_v()); });
+        }, |(), (state, inner)| inner.is_done() || state.sub.is_done() || state.next.stopped()),
+
+        forward_ec((SSWrap::new(state.clone()), inner.clone()), |(state, inner), e: Option<RxError>| {
+            state.sub.if_not_done(|| {
+                if inner.is_done() { return; }
+                inner.unsub();
+
+                if e.is_some() || state.src_done.get() {
+                    state.end(e);
+                }
+            });
+        })
+    ));
+}
+
+pub(crate) fn subscribe_single_inner<'o, SS:YesNo, VBy: RefOrVal+'o, By: RefOrVal+'o, O: Observable<'o, SS, By>+'o, F: Act<SS, VBy, O>+'o>
+(src: &impl Observable<'o, SS, VBy>, f: &Arc<F>, on_busy: OnBusy, next: impl ActNext<'o, SS, By>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS>
+{
+    let f = act_sendsync(f.clone());
+    let sub = Unsub::new();
+    let state = Arc::new(unsafe{ AnySendSync::new(SingleInnerState{
+        sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), inner: UnsafeCell::new(Unsub::done()), src_done: Cell::new(false)
+    }) });
+
+    sub.add(Unsub::with(forward_act_once(SSWrap::new(state.clone()), |state, ()| {
+        unsafe{ &*state.inner.get() }.unsub();
+    })));
+
+    sub.clone().added(src.subscribe(
+        forward_next((), (SSWrap::new(state.clone()), f, SSWrap::new(on_busy)), |(), (state, f, on_busy), v: VBy| {
+            state.sub.if_not_done(|| {
+                if let OnBusy::Drop = **on_busy {
+                    if !unsafe{ &*state.inner.get() }.is_done() { return; }
+                }
+                let o = f.call(v.into_v());
+                subscribe_inner(&*state, o);
+            });
+        }, |(), (state, _, _)| state.sub.is_done() || state.next.stopped()),
+
+        forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+            state.sub.if_not_done(|| {
+                state.src_done.replace(true);
+                if e.is_some() || unsafe{ &*state.inner.get() }.is_done() {
+                    state.end(e);
+                }
+            });
+        })
+    ))
+}
@@ -0,0 +1,132 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::cell::UnsafeCell;
+use std::collections::VecDeque;
+
+pub struct SkipLastOp<SS, VBy, Src>
+{
+    count: usize,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>)>
+}
+
+pub trait ObsSkipLastOp<SS: YesNo, VBy: RefOrVal> : Sized
+{
+    fn skip_last(self, count: usize) -> SkipLastOp<SS, VBy, Self> { SkipLastOp{ count, src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+ObsSkipLastOp<SS, VBy> for Src {}
+
+pub trait DynObsSkipLastOp<'o, SS: YesNo, VBy: IntoOwned+'o>
+{
+    fn skip_last(self, count: usize) -> DynObservable<'o, 'o, SS, Val<VBy::RAW>>;
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o>
+DynObsSkipLastOp<'o, SS, VBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn skip_last(self, count: usize) -> DynObservable<'o, 'o, SS, Val<VBy::RAW>>
+    { SkipLastOp{ count, src: self.src, PhantomData }.into_dyn() }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<VBy::RAW>>
+for SkipLastOp<SS, VBy, Src>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<VBy::RAW>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let next = SSActNextWrap::new(next);
+        let sub = Unsub::new();
+        //no mutex here because access is protected by Unsub's internal lock
+        let queue = unsafe{ AnySendSync::new((self.count, UnsafeCell::new(VecDeque::new()))) };
+
+        sub.clone().added_each(self.src.subscribe(forward_next(next, (sub, SSWrap::new(queue)), |next, (sub, queue), v: VBy| {
+            sub.if_not_done(|| {
+                let (count, queue) = (queue.0, unsafe{ &mut *queue.1.get() });
+                queue.push_back(v.into_owned());
+                if queue.len() > count {
+                    next.call(queue.pop_front().unwrap());
+                }
+            });
+        }, |s, _| s.stopped()), ec))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<VBy::RAW>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(1..6).skip_last(2).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "123ok");
+
+        let out = RefCell::new(String::new());
+        iter_clone(1..3).into_dyn().skip_last(0).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "12ok");
+    }
+
+    #[test]
+    fn huge_count()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(1..3).skip_last(usize::max_value()).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "ok");
+    }
+
+    #[test]
+    fn refs()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, String>::new()).clones();
+
+        o.skip_last(1).subscribe(|v: String| out.borrow_mut().push_str(&v), ());
+
+        i.next("a".to_owned());
+        assert_eq!(out.borrow().as_str(), "");
+
+        i.next("b".to_owned());
+        i.next("c".to_owned());
+        assert_eq!(out.borrow().as_str(), "ab");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.skip_last(7).subscribe(move |v| out.lock().unwrap().push(v), ());
+
+        ::std::thread::spawn(move || {
+            for v in 0..10 { i.next(v); }
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::Cell;
+
+pub struct SkipWhileOp<SS, Src, F>
+{
+    f: Arc<F>,
+    src: Src,
+    PhantomData: PhantomData<(SS)>
+}
+
+pub trait ObsSkipWhileOp<SS: YesNo, VBy: RefOrVal, F: Act<SS, Ref<VBy::RAW>, bool>> : Sized
+{
+    fn skip_while(self, f: F) -> SkipWhileOp<SS, Self, F> { SkipWhileOp{ f: Arc::new(f), src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal, Src: Observable<'o, SS, VBy>, F: Act<SS, Ref<VBy::RAW>, bool>+'o>
+ObsSkipWhileOp<SS, VBy, F>
+for Src {}
+
+pub trait DynObsSkipWhileOp<'o, SS: YesNo, VBy: RefOrVal+'o, F: Act<SS, Ref<VBy::RAW>, bool>+'o>
+{
+    fn skip_while(self, f: F) -> Self;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, F: Act<SS, Ref<VBy::RAW>, bool>+'o>
+DynObsSkipWhileOp<'o, SS, VBy, F>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn skip_while(self, f: F) -> Self
+    { SkipWhileOp{ f: Arc::new(f), src: self.src, PhantomData }.into_dyn() }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>, F: Act<SS, Ref<VBy::RAW>, bool>+'o>
+Observable<'o, SS, VBy>
+for SkipWhileOp<SS, Src, F>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, VBy>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let next = SSActNextWrap::new(next);
+        let f = act_sendsync(self.f.clone());
+        let sub = Unsub::new();
+        //no mutex here because access is protected by Unsub's internal lock
+        let skipping = unsafe{ AnySendSync::new(Cell::new(true)) };
+
+        sub.clone().added_each(self.src.subscribe(
+            forward_next(next, (sub, f, SSWrap::new(skipping)), |next, (sub, f, skipping), v:VBy| {
+                sub.if_not_done(|| {
+                    if skipping.get() && f.call(v.as_ref()) { return; }
+                    skipping.replace(false);
+                    next.call(v.into_v());
+                });
+            }, |s, _| s.stopped()),
+
+            ec
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, VBy>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(vec![1, 2, 3, 1, 2].into_iter()).skip_while(|v: &i32| *v < 3).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "312ok");
+
+        let out = RefCell::new(String::new());
+        iter_clone(0..3).into_dyn().skip_while(|_: &i32| true).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "ok");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.skip_while(|v: &i32| *v < 7).subscribe(move |v:&_| out.lock().unwrap().push(*v), ());
+
+        ::std::thread::spawn(move || {
+            for v in 0..10 { i.next(v); }
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), vec![7, 8, 9]);
+    }
+}
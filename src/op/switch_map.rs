@@ -0,0 +1,136 @@
+use crate::*;
+use super::single_inner::{OnBusy, subscribe_single_inner};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+pub struct SwitchMapOp<SS, VBy, Src, F, O>
+{
+    f: Arc<F>,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>, AnySendSync<O>)>
+}
+
+pub trait ObsSwitchMapOp<'o, SS: YesNo, VBy: RefOrVal, O, F: Act<SS, VBy, O>+'o> : Sized
+{
+    fn switch_map(self, f: F) -> SwitchMapOp<SS, VBy, Self, F, O> { SwitchMapOp{ f: Arc::new(f), src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, O, Src: Observable<'o, SS, VBy>+'o, F: Act<SS, VBy, O>+'o>
+ObsSwitchMapOp<'o, SS, VBy, O, F> for Src {}
+
+pub trait DynObsSwitchMapOp<'o, SS: YesNo, VBy: RefOrVal+'o, By: RefOrVal+'o, O: Observable<'o, SS, By>+'o, F: Act<SS, VBy, O>+'o>
+{
+    fn switch_map(self, f: F) -> DynObservable<'o, 'o, SS, By>;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, By: RefOrVal+'o, O: Observable<'o, SS, By>+'o, F: Act<SS, VBy, O>+'o>
+DynObsSwitchMapOp<'o, SS, VBy, By, O, F>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn switch_map(self, f: F) -> DynObservable<'o, 'o, SS, By>
+    { SwitchMapOp{ f: Arc::new(f), src: self.src, PhantomData }.into_dyn() }
+}
+
+impl<'s, 'o, SS:YesNo, VBy: RefOrVal+'o, By: RefOrVal+'o, O: Observable<'o, SS, By>+'o, Src: Observable<'o, SS, VBy>+'s, F: Act<SS, VBy, O>+'o>
+Observable<'o, SS, By>
+for SwitchMapOp<SS, VBy, Src, F, O>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, By>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    { subscribe_single_inner(&self.src, &self.f, OnBusy::Replace, next, ec) }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, By>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (a, a1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (b, b1) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.switch_map(move |v:&i32| if *v == 0 { a.clone() } else { b.clone() }).subscribe(
+            |v:&_| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        i.next(0);
+        a1.next(1);
+        i.next(1);
+        a1.next(2);
+        b1.next(3);
+
+        i.complete();
+        assert_eq!(out.borrow().as_str(), "13");
+
+        a1.complete();
+        assert_eq!(out.borrow().as_str(), "13");
+
+        b1.complete();
+        assert_eq!(out.borrow().as_str(), "13ok");
+    }
+
+    #[test]
+    fn sync_inner()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(1..4).into_dyn().switch_map(|v| iter_clone(0..v)).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        assert_eq!(out.borrow().as_str(), "001012ok");
+    }
+
+    #[test]
+    fn unsub()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (a, a1) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        let sub = o.switch_map(move |_:&i32| a.clone()).subscribe(|v:&_| out.borrow_mut().push_str(&format!("{}", v)), ());
+
+        i.next(0);
+        a1.next(1);
+        sub.unsub();
+        a1.next(2);
+
+        assert_eq!(out.borrow().as_str(), "1");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+        let (a, a1) = Arc::new(Subject::<YES, i32>::new()).clones();
+        let (b, b1) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.switch_map(move |v:&i32| if *v == 0 { a.clone() } else { b.clone() }).subscribe(
+            move |v:&_| out.lock().unwrap().push_str(&format!("{}", v)), ()
+        );
+
+        i.next(0);
+        i.next(1);
+        ::std::thread::spawn(move || {
+            a1.next(1);
+            b1.next(2);
+        }).join().ok();
+
+        assert_eq!(out1.lock().unwrap().as_str(), "2");
+    }
+}
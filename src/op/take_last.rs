@@ -0,0 +1,192 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+use std::collections::VecDeque;
+
+pub struct TakeLastOp<SS, VBy, Src>
+{
+    count: usize,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>)>
+}
+
+pub trait ObsTakeLastOp<SS: YesNo, VBy: RefOrVal> : Sized
+{
+    fn take_last(self, count: usize) -> TakeLastOp<SS, VBy, Self> { TakeLastOp{ count, src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+ObsTakeLastOp<SS, VBy> for Src {}
+
+pub trait DynObsTakeLastOp<'o, SS: YesNo, VBy: IntoOwned+'o>
+{
+    fn take_last(self, count: usize) -> DynObservable<'o, 'o, SS, Val<VBy::RAW>>;
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o>
+DynObsTakeLastOp<'o, SS, VBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn take_last(self, count: usize) -> DynObservable<'o, 'o, SS, Val<VBy::RAW>>
+    { TakeLastOp{ count, src: self.src, PhantomData }.into_dyn() }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct TakeLastState<'o, SS:YesNo, N, EC, V>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    count: usize,
+    ring: UnsafeCell<VecDeque<V>>,
+}
+
+impl<'o, SS:YesNo, N: ActNext<'o, SS, Val<V>>, EC: ActEc<'o, SS>, V> TakeLastState<'o, SS, N, EC, V>
+{
+    fn push(&self, v: V)
+    {
+        if self.count == 0 { return; }
+
+        let ring = unsafe{ &mut *self.ring.get() };
+        if ring.len() == self.count { ring.pop_front(); }
+        ring.push_back(v);
+    }
+
+    fn end(&self, e: Option<RxError>)
+    {
+        if e.is_none() {
+            while let Some(v) = unsafe{ &mut *self.ring.get() }.pop_front() {
+                if self.next.stopped() || self.sub.is_done() { break; }
+                self.next.call(v);
+            }
+        }
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<VBy::RAW>>
+for TakeLastOp<SS, VBy, Src>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<VBy::RAW>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(TakeLastState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), count: self.count,
+            ring: UnsafeCell::new(VecDeque::new())
+        }) });
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| state.push(v.into_owned()));
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| state.end(e));
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<VBy::RAW>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(1..10).take_last(3).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "789ok");
+
+        let out = RefCell::new(String::new());
+        iter_clone(1..3).into_dyn().take_last(5).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "12ok");
+
+        let out = RefCell::new(String::new());
+        iter_clone(1..3).take_last(0).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "ok");
+    }
+
+    #[test]
+    fn huge_count()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(1..3).take_last(usize::max_value()).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "12ok");
+    }
+
+    #[test]
+    fn refs()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, String>::new()).clones();
+
+        o.take_last(2).subscribe(|v: String| out.borrow_mut().push_str(&v), |_e| out.borrow_mut().push_str("ok"));
+
+        i.next("a".to_owned());
+        i.next("b".to_owned());
+        i.next("c".to_owned());
+        assert_eq!(out.borrow().as_str(), "");
+
+        i.complete();
+        assert_eq!(out.borrow().as_str(), "bcok");
+    }
+
+    #[test]
+    fn error()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.take_last(2).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |e: Option<RxError>| out.borrow_mut().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" })
+        );
+
+        i.next(1);
+        i.error(RxError::simple(None, "error"));
+        assert_eq!(out.borrow().as_str(), "err");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.take_last(2).subscribe(move |v| out.lock().unwrap().push(v), ());
+
+        ::std::thread::spawn(move || {
+            for v in 0..10 { i.next(v); }
+            i.complete();
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), vec![8, 9]);
+    }
+}
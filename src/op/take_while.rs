@@ -0,0 +1,137 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+
+pub struct TakeWhileOp<SS, Src, F>
+{
+    f: Arc<F>,
+    inclusive: bool,
+    src: Src,
+    PhantomData: PhantomData<(SS)>
+}
+
+pub trait ObsTakeWhileOp<SS: YesNo, VBy: RefOrVal, F: Act<SS, Ref<VBy::RAW>, bool>> : Sized
+{
+    fn take_while(self, f: F) -> TakeWhileOp<SS, Self, F> { TakeWhileOp{ f: Arc::new(f), inclusive: false, src: self, PhantomData } }
+    //also emits the first value that fails the predicate
+    fn take_while_inclusive(self, f: F) -> TakeWhileOp<SS, Self, F> { TakeWhileOp{ f: Arc::new(f), inclusive: true, src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal, Src: Observable<'o, SS, VBy>, F: Act<SS, Ref<VBy::RAW>, bool>+'o>
+ObsTakeWhileOp<SS, VBy, F>
+for Src {}
+
+pub trait DynObsTakeWhileOp<'o, SS: YesNo, VBy: RefOrVal+'o, F: Act<SS, Ref<VBy::RAW>, bool>+'o>
+{
+    fn take_while(self, f: F) -> Self;
+    fn take_while_inclusive(self, f: F) -> Self;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, F: Act<SS, Ref<VBy::RAW>, bool>+'o>
+DynObsTakeWhileOp<'o, SS, VBy, F>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn take_while(self, f: F) -> Self
+    { TakeWhileOp{ f: Arc::new(f), inclusive: false, src: self.src, PhantomData }.into_dyn() }
+
+    fn take_while_inclusive(self, f: F) -> Self
+    { TakeWhileOp{ f: Arc::new(f), inclusive: true, src: self.src, PhantomData }.into_dyn() }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>, F: Act<SS, Ref<VBy::RAW>, bool>+'o>
+Observable<'o, SS, VBy>
+for TakeWhileOp<SS, Src, F>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, VBy>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let next = SSActNextWrap::new(next);
+        let f = act_sendsync(self.f.clone());
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new((self.inclusive, UnsafeCell::new(Some(ec)))) });
+
+        sub.clone().added(self.src.subscribe(
+            forward_next(next, (sub.clone(), f, SSWrap::new(state.clone())), |next, (sub, f, state), v:VBy| {
+                sub.if_not_done(|| {
+                    if f.call(v.as_ref()) {
+                        return next.call(v.into_v());
+                    }
+                    if state.0 {
+                        next.call(v.into_v());
+                    }
+                    sub.unsub_then(|| unsafe{ &mut *state.1.get() }.take().map_or((), |ec| ec.call_once(None)));
+                });
+            }, |s, (sub, _, _)| s.stopped() || sub.is_done()),
+
+            forward_ec((sub, SSWrap::new(state)), |(sub, state), e:Option<RxError>| {
+                sub.unsub_then(|| unsafe{ &mut *state.1.get() }.take().map_or((), |ec| ec.call_once(e)))
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, VBy>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(1..10).take_while(|v: &i32| *v < 4).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "123ok");
+
+        let out = RefCell::new(String::new());
+        iter_clone(1..10).into_dyn().take_while_inclusive(|v: &i32| *v < 4).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "1234ok");
+    }
+
+    #[test]
+    fn subject()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.take_while(|v: &i32| *v > 0).subscribe(
+            |v: &i32| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        i.next(1);
+        i.next(2);
+        i.complete();
+        assert_eq!(out.borrow().as_str(), "12ok");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(Vec::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.take_while(|v: &i32| *v != 3).subscribe(move |v:&_| out.lock().unwrap().push(*v), ());
+
+        ::std::thread::spawn(move || {
+            for v in 0..10 { i.next(v); }
+        }).join().ok();
+
+        assert_eq!(*out1.lock().unwrap(), vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,316 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub struct TapOp<SS, Src, N, E>
+{
+    n: Arc<N>,
+    e: Arc<E>,
+    src: Src,
+    PhantomData: PhantomData<(SS)>
+}
+
+pub struct DoOnSubscribeOp<SS, Src, F>
+{
+    f: Arc<F>,
+    src: Src,
+    PhantomData: PhantomData<(SS)>
+}
+
+pub struct FinalizeOp<SS, Src, F>
+{
+    f: Arc<F>,
+    unsub_only: bool,
+    src: Src,
+    PhantomData: PhantomData<(SS)>
+}
+
+pub struct OnError<F>(F);
+
+unsafe impl<SS:YesNo, F: Act<SS, Ref<RxError>>>
+Act<SS, Ref<Option<RxError>>>
+for OnError<F>
+{
+    #[inline(always)] fn call(&self, e: *const Option<RxError>) { unsafe{ &*e }.as_ref().map_or((), |e| self.0.call(e)) }
+}
+
+pub struct OnComplete<F>(F);
+
+unsafe impl<SS:YesNo, F: Act<SS, ()>>
+Act<SS, Ref<Option<RxError>>>
+for OnComplete<F>
+{
+    #[inline(always)] fn call(&self, e: *const Option<RxError>) { if unsafe{ &*e }.is_none() { self.0.call(()) } }
+}
+
+pub trait ObsTapOp<'o, SS: YesNo, VBy: RefOrVal> : Sized
+{
+    fn tap<N: Act<SS, Ref<VBy::RAW>>+'o, E: Act<SS, Ref<Option<RxError>>>+'o>(self, next: N, ec: E) -> TapOp<SS, Self, N, E>
+    { TapOp{ n: Arc::new(next), e: Arc::new(ec), src: self, PhantomData } }
+
+    fn do_on_next<N: Act<SS, Ref<VBy::RAW>>+'o>(self, f: N) -> TapOp<SS, Self, N, ()>
+    { TapOp{ n: Arc::new(f), e: Arc::new(()), src: self, PhantomData } }
+
+    fn do_on_error<F: Act<SS, Ref<RxError>>+'o>(self, f: F) -> TapOp<SS, Self, (), OnError<F>>
+    { TapOp{ n: Arc::new(()), e: Arc::new(OnError(f)), src: self, PhantomData } }
+
+    fn do_on_complete<F: Act<SS, ()>+'o>(self, f: F) -> TapOp<SS, Self, (), OnComplete<F>>
+    { TapOp{ n: Arc::new(()), e: Arc::new(OnComplete(f)), src: self, PhantomData } }
+
+    fn do_on_subscribe<F: Act<SS, ()>+'o>(self, f: F) -> DoOnSubscribeOp<SS, Self, F>
+    { DoOnSubscribeOp{ f: Arc::new(f), src: self, PhantomData } }
+
+    //only when unsubscribed before the source ended
+    fn do_on_unsubscribe<F: Act<SS, ()>+'o>(self, f: F) -> FinalizeOp<SS, Self, F>
+    { FinalizeOp{ f: Arc::new(f), unsub_only: true, src: self, PhantomData } }
+
+    //after completion, error or unsubscription, whichever comes first
+    fn finalize<F: Act<SS, ()>+'o>(self, f: F) -> FinalizeOp<SS, Self, F>
+    { FinalizeOp{ f: Arc::new(f), unsub_only: false, src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>>
+ObsTapOp<'o, SS, VBy> for Src {}
+
+pub trait DynObsTapOp<'o, SS: YesNo, VBy: RefOrVal+'o>
+{
+    fn tap<N: Act<SS, Ref<VBy::RAW>>+'o, E: Act<SS, Ref<Option<RxError>>>+'o>(self, next: N, ec: E) -> Self;
+    fn do_on_next<N: Act<SS, Ref<VBy::RAW>>+'o>(self, f: N) -> Self;
+    fn do_on_error<F: Act<SS, Ref<RxError>>+'o>(self, f: F) -> Self;
+    fn do_on_complete<F: Act<SS, ()>+'o>(self, f: F) -> Self;
+    fn do_on_subscribe<F: Act<SS, ()>+'o>(self, f: F) -> Self;
+    fn do_on_unsubscribe<F: Act<SS, ()>+'o>(self, f: F) -> Self;
+    fn finalize<F: Act<SS, ()>+'o>(self, f: F) -> Self;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o>
+DynObsTapOp<'o, SS, VBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn tap<N: Act<SS, Ref<VBy::RAW>>+'o, E: Act<SS, Ref<Option<RxError>>>+'o>(self, next: N, ec: E) -> Self
+    { TapOp{ n: Arc::new(next), e: Arc::new(ec), src: self.src, PhantomData }.into_dyn() }
+
+    fn do_on_next<N: Act<SS, Ref<VBy::RAW>>+'o>(self, f: N) -> Self
+    { TapOp{ n: Arc::new(f), e: Arc::new(()), src: self.src, PhantomData }.into_dyn() }
+
+    fn do_on_error<F: Act<SS, Ref<RxError>>+'o>(self, f: F) -> Self
+    { TapOp{ n: Arc::new(()), e: Arc::new(OnError(f)), src: self.src, PhantomData }.into_dyn() }
+
+    fn do_on_complete<F: Act<SS, ()>+'o>(self, f: F) -> Self
+    { TapOp{ n: Arc::new(()), e: Arc::new(OnComplete(f)), src: self.src, PhantomData }.into_dyn() }
+
+    fn do_on_subscribe<F: Act<SS, ()>+'o>(self, f: F) -> Self
+    { DoOnSubscribeOp{ f: Arc::new(f), src: self.src, PhantomData }.into_dyn() }
+
+    fn do_on_unsubscribe<F: Act<SS, ()>+'o>(self, f: F) -> Self
+    { FinalizeOp{ f: Arc::new(f), unsub_only: true, src: self.src, PhantomData }.into_dyn() }
+
+    fn finalize<F: Act<SS, ()>+'o>(self, f: F) -> Self
+    { FinalizeOp{ f: Arc::new(f), unsub_only: false, src: self.src, PhantomData }.into_dyn() }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>, N: Act<SS, Ref<VBy::RAW>>+'o, E: Act<SS, Ref<Option<RxError>>>+'o>
+Observable<'o, SS, VBy>
+for TapOp<SS, Src, N, E>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, VBy>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let next = SSActNextWrap::new(next);
+        let n = act_sendsync(self.n.clone());
+        let e = act_sendsync(self.e.clone());
+        let sub = Unsub::new();
+
+        sub.clone().added(self.src.subscribe(
+            forward_next(next, (sub.clone(), n), |next, (sub, n), v: VBy| {
+                sub.if_not_done(|| {
+                    n.call(v.as_ref());
+                    next.call(v.into_v());
+                });
+            }, |next, (sub, _)| next.stopped() || sub.is_done()),
+
+            forward_ec((SSActEcWrap::new(ec), e, sub), |(ec, e, sub), err: Option<RxError>| {
+                sub.if_not_done(|| sub.unsub_then(|| {
+                    e.call(&err);
+                    ec.into_inner().call_once(err);
+                }));
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, VBy>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>, F: Act<SS, ()>+'o>
+Observable<'o, SS, VBy>
+for DoOnSubscribeOp<SS, Src, F>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, VBy>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        self.f.call(());
+        self.src.subscribe(next, ec)
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, VBy>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>, F: Act<SS, ()>+'o>
+Observable<'o, SS, VBy>
+for FinalizeOp<SS, Src, F>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, VBy>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let next = SSActNextWrap::new(next);
+        let f = act_sendsync(self.f.clone());
+        let sub = Unsub::new();
+        //(unsub_only, ended): set before `sub` is unsubscribed by the source's end
+        let state = Arc::new((self.unsub_only, AtomicBool::new(false)));
+
+        sub.add(Unsub::with(forward_act_once((f, SSWrap::new(state.clone())), |(f, state), ()| {
+            if !(state.0 && state.1.load(Ordering::Acquire)) { f.call(()); }
+        })));
+
+        //the source isn't linked back to `sub`: a `Subject` unsubscribes before calling `ec`, which would run `f` too early
+        sub.clone().added(self.src.subscribe(
+            forward_next(next, sub.clone(), |next, sub, v: VBy| {
+                sub.if_not_done(|| next.call(v.into_v()));
+            }, |s, sub| s.stopped() || sub.is_done()),
+
+            forward_ec((SSActEcWrap::new(ec), sub, SSWrap::new(state)), |(ec, sub, state), e: Option<RxError>| {
+                state.1.store(true, Ordering::Release);
+                sub.if_not_done(|| ec.into_inner().call_once(e));
+                sub.unsub();
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, VBy>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::Cell;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(1..4).tap(
+            |v: &i32| out.borrow_mut().push_str(&format!("t{}", v)),
+            |e: &Option<RxError>| out.borrow_mut().push_str(if e.is_some() { "terr" } else { "tok" })
+        ).subscribe(
+            |v| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        assert_eq!(out.borrow().as_str(), "t11t22t33tokok");
+    }
+
+    #[test]
+    fn unsub()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        let sub = o.tap(
+            |v: &i32| out.borrow_mut().push_str(&format!("t{}", v)),
+            |_e: &Option<RxError>| out.borrow_mut().push_str("tc")
+        ).subscribe(|_: &i32| {}, ());
+
+        i.next(1);
+        sub.unsub();
+        i.next(2);
+        i.complete();
+        assert_eq!(out.borrow().as_str(), "t1");
+    }
+
+    #[test]
+    fn do_on()
+    {
+        let out = RefCell::new(String::new());
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.into_dyn()
+            .do_on_subscribe(|| out.borrow_mut().push_str("s"))
+            .do_on_next(|v: &i32| out.borrow_mut().push_str(&format!("n{}", v)))
+            .do_on_complete(|| out.borrow_mut().push_str("c"))
+            .do_on_error(|_e: &RxError| out.borrow_mut().push_str("e"))
+            .subscribe(|_: &i32| {}, |e: Option<RxError>| { e.map(|e| e.set_handled()); });
+
+        i.next(1);
+        i.error(RxError::simple(None, "error"));
+        assert_eq!(out.borrow().as_str(), "sn1e");
+    }
+
+    #[test]
+    fn finalize()
+    {
+        let n = Cell::new(0);
+        let out = RefCell::new(String::new());
+
+        Of::value(1).finalize(|| { n.replace(n.get() + 1); out.borrow_mut().push_str("f"); }).subscribe(
+            |v: &i32| out.borrow_mut().push_str(&format!("{}", v)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+        assert_eq!(out.borrow().as_str(), "1okf");
+        assert_eq!(n.get(), 1);
+
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let sub = o.finalize(|| { n.replace(n.get() + 1); }).subscribe(|_: &i32| {}, |e: Option<RxError>| { e.map(|e| e.set_handled()); });
+        i.error(RxError::simple(None, "error"));
+        sub.unsub();
+        assert_eq!(n.get(), 2);
+
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let sub = o.finalize(|| { n.replace(n.get() + 1); }).subscribe(|_: &i32| {}, ());
+        sub.unsub();
+        i.complete();
+        assert_eq!(n.get(), 3);
+    }
+
+    #[test]
+    fn do_on_unsubscribe()
+    {
+        let n = Cell::new(0);
+
+        Of::value(1).do_on_unsubscribe(|| { n.replace(n.get() + 1); }).subscribe(|_: &i32| {}, ());
+        assert_eq!(n.get(), 0);
+
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let sub = o.do_on_unsubscribe(|| { n.replace(n.get() + 1); }).subscribe(|_: &i32| {}, ());
+        i.next(1);
+        sub.unsub();
+        sub.unsub();
+        assert_eq!(n.get(), 1);
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (n, n1) = Arc::new(Mutex::new(0)).clones();
+        let (f, f1) = Arc::new(Mutex::new(0)).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.do_on_next(move |v: &i32| *n.lock().unwrap() += *v).finalize(move || *f.lock().unwrap() += 1).subscribe(|_: &i32| {}, ());
+
+        ::std::thread::spawn(move || {
+            for v in 0..4 { i.next(v); }
+            i.complete();
+        }).join().ok();
+
+        assert_eq!(*n1.lock().unwrap(), 6);
+        assert_eq!(*f1.lock().unwrap(), 1);
+    }
+}
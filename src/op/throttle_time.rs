@@ -0,0 +1,217 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::Cell;
+use std::cell::UnsafeCell;
+use std::time::Duration;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ThrottleConfig
+{
+    pub leading: bool,
+    pub trailing: bool,
+}
+
+impl Default for ThrottleConfig
+{
+    fn default() -> ThrottleConfig { ThrottleConfig{ leading: true, trailing: false } }
+}
+
+pub struct ThrottleTimeOp<SS, VBy, Src, Sch>
+{
+    due: Duration,
+    scheduler: Arc<Sch>,
+    config: ThrottleConfig,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>)>
+}
+
+pub trait ObsThrottleTimeOp<SS: YesNo, VBy: RefOrVal, Sch: Scheduler<SS>> : Sized
+{
+    fn throttle_time(self, due: Duration, scheduler: Sch, config: ThrottleConfig) -> ThrottleTimeOp<SS, VBy, Self, Sch>
+    { ThrottleTimeOp{ due, scheduler: Arc::new(scheduler), config, src: self, PhantomData } }
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Src: Observable<'static, SS, VBy>, Sch: Scheduler<SS>+'static>
+ObsThrottleTimeOp<SS, VBy, Sch> for Src {}
+
+pub trait DynObsThrottleTimeOp<SS: YesNo, VBy: IntoOwned+'static, Sch: Scheduler<SS>+'static>
+{
+    fn throttle_time(self, due: Duration, scheduler: Sch, config: ThrottleConfig) -> DynObservable<'static, 'static, SS, Val<VBy::RAW>>;
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Sch: Scheduler<SS>+'static>
+DynObsThrottleTimeOp<SS, VBy, Sch>
+for DynObservable<'static, 'static, SS, VBy>
+{
+    fn throttle_time(self, due: Duration, scheduler: Sch, config: ThrottleConfig) -> DynObservable<'static, 'static, SS, Val<VBy::RAW>>
+    { ThrottleTimeOp{ due, scheduler: Arc::new(scheduler), config, src: self.src, PhantomData }.into_dyn() }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct ThrottleTimeState<SS:YesNo, N, EC, V, Sch>
+{
+    sub: Unsub<'static, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    due: Duration,
+    scheduler: Arc<Sch>,
+    config: ThrottleConfig,
+    pending: UnsafeCell<Option<V>>,
+    throttled: Cell<bool>,
+    timer: UnsafeCell<Unsub<'static, SS>>,
+}
+
+impl<SS:YesNo, N: ActNext<'static, SS, Val<V>>, EC: ActEc<'static, SS>, V: 'static, Sch: Scheduler<SS>+'static> ThrottleTimeState<SS, N, EC, V, Sch>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+
+    fn emit(&self, v: V)
+    {
+        if !self.next.stopped() { self.next.call(v); }
+    }
+}
+
+fn open_window<SS:YesNo, N: ActNext<'static, SS, Val<V>>, EC: ActEc<'static, SS>, V: 'static, Sch: Scheduler<SS>+'static>
+(state: &Arc<AnySendSync<ThrottleTimeState<SS, N, EC, V, Sch>>>)
+{
+    state.throttled.replace(true);
+
+    let timer = state.scheduler.schedule(Some(state.due), forward_act_once(SSWrap::new(state.clone()), |state, ()| {
+        state.sub.if_not_done(|| {
+            state.throttled.replace(false);
+            //a trailing emission starts a new window of its own
+            if let Some(v) = unsafe{ &mut *state.pending.get() }.take() {
+                state.emit(v);
+                open_window(&*state);
+            }
+        });
+        Unsub::done()
+    }));
+
+    if state.sub.is_done() { timer.unsub(); }
+    else { unsafe{ *state.timer.get() = timer; } }
+}
+
+impl<SS:YesNo, VBy: IntoOwned+'static, Src: Observable<'static, SS, VBy>, Sch: Scheduler<SS>+'static>
+Observable<'static, SS, Val<VBy::RAW>>
+for ThrottleTimeOp<SS, VBy, Src, Sch>
+{
+    fn subscribe(&self, next: impl ActNext<'static, SS, Val<VBy::RAW>>, ec: impl ActEc<'static, SS>) -> Unsub<'static, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(ThrottleTimeState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), due: self.due, scheduler: self.scheduler.clone(), config: self.config,
+            pending: UnsafeCell::new(None), throttled: Cell::new(false), timer: UnsafeCell::new(Unsub::done())
+        }) });
+
+        sub.add(Unsub::with(forward_act_once(SSWrap::new(state.clone()), |state, ()| {
+            unsafe{ &*state.timer.get() }.unsub();
+        })));
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| {
+                    if state.throttled.get() {
+                        if state.config.trailing { unsafe{ *state.pending.get() = Some(v.into_owned()); } }
+                        return;
+                    }
+
+                    if state.config.leading { state.emit(v.into_owned()); }
+                    else if state.config.trailing { unsafe{ *state.pending.get() = Some(v.into_owned()); } }
+                    open_window(&*state);
+                });
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| {
+                    unsafe{ &*state.timer.get() }.unsub();
+                    if e.is_none() {
+                        if let Some(v) = unsafe{ &mut *state.pending.get() }.take() { state.emit(v); }
+                    }
+                    state.end(e);
+                });
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'static, SS, Val<VBy::RAW>>>, ec: Box<ActEcBox<'static, SS>>) -> Unsub<'static, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn leading()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1, out2) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.throttle_time(Duration::from_millis(50), sch.clone(), ThrottleConfig::default()).subscribe(
+            move |v| out.lock().unwrap().push_str(&format!("{}", v)),
+            move |_e| out1.lock().unwrap().push_str("ok")
+        );
+
+        i.next(1);
+        i.next(2);
+        i.next(3);
+        assert_eq!(out2.lock().unwrap().as_str(), "1");
+
+        ::std::thread::sleep(Duration::from_millis(200));
+        i.next(4);
+        i.next(5);
+        i.complete();
+        assert_eq!(out2.lock().unwrap().as_str(), "14ok");
+    }
+
+    #[test]
+    fn trailing()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.into_dyn().throttle_time(Duration::from_millis(50), sch.clone(), ThrottleConfig{ leading: true, trailing: true }).subscribe(
+            move |v| out.lock().unwrap().push_str(&format!("{}", v)), ()
+        );
+
+        i.next(1);
+        i.next(2);
+        i.next(3);
+        assert_eq!(out1.lock().unwrap().as_str(), "1");
+
+        ::std::thread::sleep(Duration::from_millis(200));
+        assert_eq!(out1.lock().unwrap().as_str(), "13");
+    }
+
+    #[test]
+    fn unsub_cancels_trailing()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        let sub = o.throttle_time(Duration::from_millis(30), sch.clone(), ThrottleConfig{ leading: false, trailing: true }).subscribe(
+            move |v| out.lock().unwrap().push_str(&format!("{}", v)), ()
+        );
+
+        i.next(1);
+        i.next(2);
+        sub.unsub();
+
+        ::std::thread::sleep(Duration::from_millis(100));
+        assert_eq!(out1.lock().unwrap().as_str(), "");
+    }
+}
@@ -0,0 +1,229 @@
+use crate::*;
+use std::sync::Arc;
+use std::cell::Cell;
+use std::cell::UnsafeCell;
+use std::time::Duration;
+
+pub struct TimeoutOp<SS:YesNo, VBy: RefOrVal, Src, Sch>
+{
+    due: Duration,
+    scheduler: Arc<Sch>,
+    fallback: Option<DynObservable<'static, 'static, SS, VBy>>,
+    src: Src,
+}
+
+pub trait ObsTimeoutOp<SS: YesNo, VBy: RefOrVal, Sch: Scheduler<SS>> : Sized
+{
+    fn timeout(self, due: Duration, scheduler: Sch) -> TimeoutOp<SS, VBy, Self, Sch>
+    { TimeoutOp{ due, scheduler: Arc::new(scheduler), fallback: None, src: self } }
+
+    fn timeout_with(self, due: Duration, scheduler: Sch, fallback: impl Observable<'static, SS, VBy>+'static) -> TimeoutOp<SS, VBy, Self, Sch>
+    { TimeoutOp{ due, scheduler: Arc::new(scheduler), fallback: Some(fallback.into_dyn()), src: self } }
+}
+
+impl<SS:YesNo, VBy: RefOrVal+'static, Src: Observable<'static, SS, VBy>, Sch: Scheduler<SS>+'static>
+ObsTimeoutOp<SS, VBy, Sch> for Src {}
+
+pub trait DynObsTimeoutOp<SS: YesNo, VBy: RefOrVal+'static, Sch: Scheduler<SS>+'static>
+{
+    fn timeout(self, due: Duration, scheduler: Sch) -> DynObservable<'static, 'static, SS, VBy>;
+    fn timeout_with(self, due: Duration, scheduler: Sch, fallback: impl Observable<'static, SS, VBy>+'static) -> DynObservable<'static, 'static, SS, VBy>;
+}
+
+impl<SS:YesNo, VBy: RefOrVal+'static, Sch: Scheduler<SS>+'static>
+DynObsTimeoutOp<SS, VBy, Sch>
+for DynObservable<'static, 'static, SS, VBy>
+{
+    fn timeout(self, due: Duration, scheduler: Sch) -> DynObservable<'static, 'static, SS, VBy>
+    { TimeoutOp{ due, scheduler: Arc::new(scheduler), fallback: None, src: self.src }.into_dyn() }
+
+    fn timeout_with(self, due: Duration, scheduler: Sch, fallback: impl Observable<'static, SS, VBy>+'static) -> DynObservable<'static, 'static, SS, VBy>
+    { TimeoutOp{ due, scheduler: Arc::new(scheduler), fallback: Some(fallback.into_dyn()), src: self.src }.into_dyn() }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct TimeoutState<SS:YesNo, VBy: RefOrVal, N, EC, Sch>
+{
+    sub: Unsub<'static, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    due: Duration,
+    scheduler: Arc<Sch>,
+    fallback: Option<DynObservable<'static, 'static, SS, VBy>>,
+    id: Cell<usize>,
+    timed_out: Cell<bool>,
+    timer: UnsafeCell<Unsub<'static, SS>>,
+    src: UnsafeCell<Unsub<'static, SS>>,
+}
+
+impl<SS:YesNo, VBy: RefOrVal+'static, N: ActNext<'static, SS, VBy>, EC: ActEc<'static, SS>, Sch: Scheduler<SS>+'static> TimeoutState<SS, VBy, N, EC, Sch>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+}
+
+fn schedule_timeout<SS:YesNo, VBy: RefOrVal+'static, N: ActNext<'static, SS, VBy>, EC: ActEc<'static, SS>, Sch: Scheduler<SS>+'static>
+(state: &Arc<AnySendSync<TimeoutState<SS, VBy, N, EC, Sch>>>)
+{
+    unsafe{ ::std::mem::replace(&mut *state.timer.get(), Unsub::done()) }.unsub();
+
+    //`id` tells a timer that fired but lost the race for the lock against a new value that it's stale
+    let timer = state.scheduler.schedule(Some(state.due), forward_act_once(SSWrap::new((state.clone(), state.id.get())), |caps, ()| {
+        let (state, id) = caps.into_inner();
+        state.sub.if_not_done(|| {
+            if state.id.get() != id || state.timed_out.get() { return; }
+            state.timed_out.replace(true);
+            unsafe{ &*state.src.get() }.unsub();
+
+            match state.fallback.clone() {
+                Some(fallback) => subscribe_fallback(&state, fallback),
+                None => state.end(Some(RxError::timeout()))
+            }
+        });
+        Unsub::done()
+    }));
+
+    if state.sub.is_done() { timer.unsub(); }
+    else { unsafe{ *state.timer.get() = timer; } }
+}
+
+fn subscribe_fallback<SS:YesNo, VBy: RefOrVal+'static, N: ActNext<'static, SS, VBy>, EC: ActEc<'static, SS>, Sch: Scheduler<SS>+'static>
+(state: &Arc<AnySendSync<TimeoutState<SS, VBy, N, EC, Sch>>>, fallback: DynObservable<'static, 'static, SS, VBy>)
+{
+    state.sub.add(fallback.subscribe(
+        forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+            state.sub.if_not_done(|| state.next.call(v.into_v()));
+        }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+        forward_ec(SSWrap::new(state.clone()), |state, e: Option<RxError>| {
+            state.sub.if_not_done(|| state.end(e));
+        })
+    ));
+}
+
+impl<SS:YesNo, VBy: RefOrVal+'static, Src: Observable<'static, SS, VBy>, Sch: Scheduler<SS>+'static>
+Observable<'static, SS, VBy>
+for TimeoutOp<SS, VBy, Src, Sch>
+{
+    fn subscribe(&self, next: impl ActNext<'static, SS, VBy>, ec: impl ActEc<'static, SS>) -> Unsub<'static, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(TimeoutState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), due: self.due, scheduler: self.scheduler.clone(), fallback: self.fallback.clone(),
+            id: Cell::new(0), timed_out: Cell::new(false), timer: UnsafeCell::new(Unsub::done()), src: UnsafeCell::new(Unsub::done())
+        }) });
+
+        sub.add(Unsub::with(forward_act_once(SSWrap::new(state.clone()), |state, ()| {
+            unsafe{ &*state.timer.get() }.unsub();
+        })));
+
+        sub.if_not_done(|| schedule_timeout(&state));
+
+        let src = self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| {
+                    if state.timed_out.get() { return; }
+                    state.id.replace(state.id.get().wrapping_add(1));
+                    schedule_timeout(&*state);
+                    state.next.call(v.into_v());
+                });
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state.clone()), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| {
+                    //the source was already switched away from: its late error is dropped
+                    if state.timed_out.get() {
+                        if let Some(e) = e { e.set_handled(); }
+                        return;
+                    }
+                    unsafe{ &*state.timer.get() }.unsub();
+                    state.end(e);
+                });
+            })
+        );
+
+        sub.if_not_done(|| unsafe{ *state.src.get() = src.clone(); });
+        sub.added(src)
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'static, SS, VBy>>, ec: Box<ActEcBox<'static, SS>>) -> Unsub<'static, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn smoke()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1, out2) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.timeout(Duration::from_millis(100), sch.clone()).subscribe(
+            move |v:&_| out.lock().unwrap().push_str(&format!("{}", v)),
+            move |e: Option<RxError>| out1.lock().unwrap().push_str(if e.and_then(|e| e.set_handled().timeout_error()).is_some() { "timeout" } else { "ok" })
+        );
+
+        for v in 0..3 {
+            ::std::thread::sleep(Duration::from_millis(20));
+            i.next(v);
+        }
+        assert_eq!(out2.lock().unwrap().as_str(), "012");
+
+        ::std::thread::sleep(Duration::from_millis(300));
+        assert_eq!(out2.lock().unwrap().as_str(), "012timeout");
+
+        i.next(3);
+        assert_eq!(out2.lock().unwrap().as_str(), "012timeout");
+    }
+
+    #[test]
+    fn complete_in_time()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1, out2) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.into_dyn().timeout(Duration::from_millis(50), sch.clone()).subscribe(
+            move |v:&_| out.lock().unwrap().push_str(&format!("{}", v)),
+            move |e: Option<RxError>| out1.lock().unwrap().push_str(if e.map(|e| e.set_handled()).is_some() { "timeout" } else { "ok" })
+        );
+
+        i.next(1);
+        i.complete();
+
+        ::std::thread::sleep(Duration::from_millis(150));
+        assert_eq!(out2.lock().unwrap().as_str(), "1ok");
+    }
+
+    #[test]
+    fn fallback()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1, out2) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.timeout_with(Duration::from_millis(50), sch.clone(), Of::<YES, i32>::value(100)).subscribe(
+            move |v:&_| out.lock().unwrap().push_str(&format!("{},", v)),
+            move |_e| out1.lock().unwrap().push_str("ok")
+        );
+
+        i.next(1);
+
+        ::std::thread::sleep(Duration::from_millis(200));
+        assert_eq!(out2.lock().unwrap().as_str(), "1,100,ok");
+
+        i.next(2);
+        assert_eq!(out2.lock().unwrap().as_str(), "1,100,ok");
+    }
+}
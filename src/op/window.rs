@@ -0,0 +1,213 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+use std::time::Duration;
+
+pub struct WindowOp<'o, SS:YesNo, VBy, Src, SVBy: RefOrVal>
+{
+    src: Src,
+    sig: DynObservable<'o, 'o, SS, SVBy>,
+    PhantomData: PhantomData<AnySendSync<VBy>>
+}
+
+pub trait ObsWindowOp<'o, SS:YesNo, VBy: RefOrVal, SVBy: RefOrVal> : Sized
+{
+    fn window(self, sig: impl Observable<'o, SS, SVBy>+'o) -> WindowOp<'o, SS, VBy, Self, SVBy>
+    {
+        WindowOp{ src: self, sig: sig.into_dyn(), PhantomData }
+    }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, SVBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>>
+ObsWindowOp<'o, SS, VBy, SVBy>
+for Src {}
+
+pub trait DynObsWindowOp<'o, SS:YesNo, VBy: RefOrVal, SVBy: RefOrVal> : Sized
+{
+    fn window(self, sig: impl Observable<'o, SS, SVBy>+'o) -> DynObservable<'o, 'o, SS, Val<DynObservable<'o, 'o, SS, Ref<VBy::RAW>>>>;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, SVBy: RefOrVal+'o>
+DynObsWindowOp<'o, SS, VBy, SVBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn window(self, sig: impl Observable<'o, SS, SVBy>+'o) -> DynObservable<'o, 'o, SS, Val<DynObservable<'o, 'o, SS, Ref<VBy::RAW>>>>
+    {
+        WindowOp{ src: self.src, sig: sig.into_dyn(), PhantomData }.into_dyn()
+    }
+}
+
+pub trait ObsWindowTimeOp<SS:YesNo, VBy: RefOrVal, Sch: SchedulerPeriodic<SS>> : Sized
+{
+    fn window_time(self, period: Duration, scheduler: Sch) -> WindowOp<'static, SS, VBy, Self, Val<usize>>;
+}
+
+impl<SS:YesNo, VBy: RefOrVal+'static, Src: Observable<'static, SS, VBy>, Sch: SchedulerPeriodic<SS>+'static>
+ObsWindowTimeOp<SS, VBy, Sch>
+for Src
+{
+    fn window_time(self, period: Duration, scheduler: Sch) -> WindowOp<'static, SS, VBy, Self, Val<usize>>
+    {
+        self.window(Timer::new(period, scheduler))
+    }
+}
+
+pub trait DynObsWindowTimeOp<SS:YesNo, VBy: RefOrVal, Sch: SchedulerPeriodic<SS>> : Sized
+{
+    fn window_time(self, period: Duration, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<DynObservable<'static, 'static, SS, Ref<VBy::RAW>>>>;
+}
+
+impl<SS:YesNo, VBy: RefOrVal+'static, Sch: SchedulerPeriodic<SS>+'static>
+DynObsWindowTimeOp<SS, VBy, Sch>
+for DynObservable<'static, 'static, SS, VBy>
+{
+    fn window_time(self, period: Duration, scheduler: Sch) -> DynObservable<'static, 'static, SS, Val<DynObservable<'static, 'static, SS, Ref<VBy::RAW>>>>
+    {
+        WindowOp{ src: self.src, sig: Timer::new(period, scheduler).into_dyn(), PhantomData }.into_dyn()
+    }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct WindowState<'o, SS:YesNo, N, EC, V>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    window: UnsafeCell<Arc<Subject<'o, SS, V>>>,
+}
+
+impl<'o, SS:YesNo, N: ActNext<'o, SS, Val<DynObservable<'o, 'o, SS, Ref<V>>>>, EC: ActEc<'o, SS>, V: 'o> WindowState<'o, SS, N, EC, V>
+{
+    fn open(&self)
+    {
+        let window = Arc::new(Subject::new());
+        unsafe{ ::std::mem::replace(&mut *self.window.get(), window.clone()) }.complete();
+        if !self.next.stopped() { self.next.call(window.into_dyn()); }
+    }
+
+    fn end(&self, e: Option<RxError>)
+    {
+        unsafe{ &*self.window.get() }.ec(e.clone());
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, SVBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<DynObservable<'o, 'o, SS, Ref<VBy::RAW>>>>
+for WindowOp<'o, SS, VBy, Src, SVBy>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<DynObservable<'o, 'o, SS, Ref<VBy::RAW>>>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(WindowState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), window: UnsafeCell::new(Arc::new(Subject::new()))
+        }) });
+
+        sub.if_not_done(|| state.open());
+
+        sub.add(self.sig.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, _v: SVBy| {
+                state.sub.if_not_done(|| state.open());
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state.clone()), |state, e: Option<RxError>| {
+                if e.is_some() { state.sub.if_not_done(|| state.end(e)); }
+            })
+        ));
+
+        if sub.is_done() { return sub; }
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| unsafe{ &*state.window.get() }.next_ref(v.as_ref()));
+            }, |(), state| state.sub.is_done()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| state.end(e));
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<DynObservable<'o, 'o, SS, Ref<VBy::RAW>>>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn smoke()
+    {
+        let out = Rc::new(RefCell::new(String::new()));
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (s, sig) = Rc::new(Subject::<NO, ()>::new()).clones();
+
+        let out1 = out.clone();
+        o.window(sig).subscribe(
+            move |w: DynObservable<NO, Ref<i32>>| {
+                let (out, out1) = out1.clone().clones();
+                out.borrow_mut().push_str("[");
+                w.subscribe(move |v:&_| out.borrow_mut().push_str(&format!("{}", v)), move |_e| out1.borrow_mut().push_str("]"));
+            },
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        i.next(1);
+        i.next(2);
+        s.next(());
+        s.next(());
+        i.next(3);
+        assert_eq!(out.borrow().as_str(), "[12][][3");
+
+        i.complete();
+        assert_eq!(out.borrow().as_str(), "[12][][3]ok");
+    }
+
+    #[test]
+    fn error()
+    {
+        let out = Rc::new(RefCell::new(String::new()));
+        let o = Rc::new(iter_once(vec![1, 2].into_iter()));
+        o.clone().subscribe((), ());
+
+        let out1 = out.clone();
+        o.window(Of::<NO, ()>::empty()).subscribe(
+            move |w: DynObservable<NO, Ref<i32>>| {
+                let out1 = out1.clone();
+                w.subscribe((), move |e: Option<RxError>| out1.borrow_mut().push_str(if e.map(|e| e.set_handled()).is_some() { "window err," } else { "" }));
+            },
+            |e: Option<RxError>| out.borrow_mut().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" })
+        );
+
+        assert_eq!(out.borrow().as_str(), "window err,err");
+    }
+
+    #[test]
+    fn window_time()
+    {
+        let sch = Arc::new(NewThreadScheduler::new(Arc::new(DefaultThreadFac)));
+        let (out, out1) = Arc::new(Mutex::new(0)).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.into_dyn().window_time(Duration::from_millis(50), sch.clone()).subscribe(move |w: DynObservable<YES, Ref<i32>>| {
+            let out = out.clone();
+            w.subscribe(move |v:&_| *out.lock().unwrap() += v, ());
+        }, ());
+
+        i.next(1);
+        i.next(2);
+        ::std::thread::sleep(Duration::from_millis(120));
+        i.next(3);
+        assert_eq!(*out1.lock().unwrap(), 6);
+    }
+}
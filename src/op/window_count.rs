@@ -0,0 +1,206 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::Cell;
+use std::cell::UnsafeCell;
+use std::collections::VecDeque;
+
+pub struct WindowCountOp<SS, VBy, Src>
+{
+    count: usize,
+    skip: usize,
+    src: Src,
+    PhantomData: PhantomData<(SS, AnySendSync<VBy>)>
+}
+
+pub trait ObsWindowCountOp<SS: YesNo, VBy: RefOrVal> : Sized
+{
+    //completes immediately when `count == 0`
+    fn window_count(self, count: usize) -> WindowCountOp<SS, VBy, Self> { self.window_count_skip(count, count) }
+
+    //opens a new window every `skip` values, so windows overlap when `skip < count`
+    fn window_count_skip(self, count: usize, skip: usize) -> WindowCountOp<SS, VBy, Self>
+    { WindowCountOp{ count, skip, src: self, PhantomData } }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>>
+ObsWindowCountOp<SS, VBy> for Src {}
+
+pub trait DynObsWindowCountOp<'o, SS: YesNo, VBy: RefOrVal+'o>
+{
+    fn window_count(self, count: usize) -> DynObservable<'o, 'o, SS, Val<DynObservable<'o, 'o, SS, Ref<VBy::RAW>>>>;
+    fn window_count_skip(self, count: usize, skip: usize) -> DynObservable<'o, 'o, SS, Val<DynObservable<'o, 'o, SS, Ref<VBy::RAW>>>>;
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o>
+DynObsWindowCountOp<'o, SS, VBy>
+for DynObservable<'o, 'o, SS, VBy>
+{
+    fn window_count(self, count: usize) -> DynObservable<'o, 'o, SS, Val<DynObservable<'o, 'o, SS, Ref<VBy::RAW>>>>
+    { self.window_count_skip(count, count) }
+
+    fn window_count_skip(self, count: usize, skip: usize) -> DynObservable<'o, 'o, SS, Val<DynObservable<'o, 'o, SS, Ref<VBy::RAW>>>>
+    { WindowCountOp{ count, skip, src: self.src, PhantomData }.into_dyn() }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct WindowCountState<'o, SS:YesNo, N, EC, V>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    count: usize,
+    skip: usize,
+    index: Cell<usize>,
+    windows: UnsafeCell<VecDeque<(Arc<Subject<'o, SS, V>>, usize)>>,
+}
+
+impl<'o, SS:YesNo, N: ActNext<'o, SS, Val<DynObservable<'o, 'o, SS, Ref<V>>>>, EC: ActEc<'o, SS>, V: 'o> WindowCountState<'o, SS, N, EC, V>
+{
+    fn open(&self)
+    {
+        let window = Arc::new(Subject::new());
+        unsafe{ &mut *self.windows.get() }.push_back((window.clone(), 0));
+        if !self.next.stopped() { self.next.call(window.into_dyn()); }
+    }
+
+    fn push(&self, v: &V)
+    {
+        for (window, n) in unsafe{ &mut *self.windows.get() }.iter_mut() {
+            *n += 1;
+            window.next_ref(v);
+        }
+
+        let full = unsafe{ &*self.windows.get() }.front().map_or(false, |(_, n)| *n == self.count);
+        if full {
+            unsafe{ &mut *self.windows.get() }.pop_front().unwrap().0.complete();
+        }
+
+        self.index.replace(self.index.get().wrapping_add(1));
+        if self.index.get() % self.skip == 0 { self.open(); }
+    }
+
+    fn end(&self, e: Option<RxError>)
+    {
+        for (window, _) in unsafe{ &mut *self.windows.get() }.drain(..) {
+            window.ec(e.clone());
+        }
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+}
+
+impl<'o, SS:YesNo, VBy: RefOrVal+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<DynObservable<'o, 'o, SS, Ref<VBy::RAW>>>>
+for WindowCountOp<SS, VBy, Src>
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<DynObservable<'o, 'o, SS, Ref<VBy::RAW>>>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        if self.count == 0 || self.skip == 0 {
+            ec.call_once(None);
+            return Unsub::done();
+        }
+
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(WindowCountState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), count: self.count, skip: self.skip,
+            index: Cell::new(0), windows: UnsafeCell::new(VecDeque::new())
+        }) });
+
+        sub.if_not_done(|| state.open());
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.sub.if_not_done(|| state.push(v.as_ref()));
+            }, |(), state| state.sub.is_done()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| {
+                state.sub.if_not_done(|| state.end(e));
+            })
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<DynObservable<'o, 'o, SS, Ref<VBy::RAW>>>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    fn collect(out: &Rc<RefCell<String>>) -> impl Fn(DynObservable<NO, Ref<i32>>)
+    {
+        let out = out.clone();
+        move |w| {
+            let (out, out1) = out.clone().clones();
+            out.borrow_mut().push_str("[");
+            w.subscribe(move |v:&_| out.borrow_mut().push_str(&format!("{}", v)), move |_e| out1.borrow_mut().push_str("]"));
+        }
+    }
+
+    #[test]
+    fn smoke()
+    {
+        let out = Rc::new(RefCell::new(String::new()));
+        let (i, o) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        o.window_count(2).subscribe(collect(&out), ());
+
+        for v in 0..5 { i.next(v); }
+        assert_eq!(out.borrow().as_str(), "[01][23][4");
+
+        i.complete();
+        assert_eq!(out.borrow().as_str(), "[01][23][4]");
+    }
+
+    #[test]
+    fn skip()
+    {
+        let out = Rc::new(RefCell::new(String::new()));
+        iter_clone(0..4).window_count_skip(2, 1).subscribe(collect(&out), ());
+        assert_eq!(out.borrow().as_str(), "[0[11][22][33][]]");
+
+        let out = Rc::new(RefCell::new(String::new()));
+        iter_clone(0..5).into_dyn().window_count_skip(1, 2).subscribe(collect(&out), ());
+        assert_eq!(out.borrow().as_str(), "[0][2][4]");
+    }
+
+    #[test]
+    fn zero()
+    {
+        let out = Rc::new(RefCell::new(String::new()));
+        let out1 = out.clone();
+        iter_clone(0..3).window_count(0).subscribe(collect(&out), move |_e| out1.borrow_mut().push_str("ok"));
+        assert_eq!(out.borrow().as_str(), "ok");
+
+        let out = Rc::new(RefCell::new(String::new()));
+        let out1 = out.clone();
+        iter_clone(0..3).window_count_skip(2, 0).subscribe(collect(&out), move |_e| out1.borrow_mut().push_str("ok"));
+        assert_eq!(out.borrow().as_str(), "ok");
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(String::new())).clones();
+        let (i, o) = Arc::new(Subject::<YES, i32>::new()).clones();
+
+        o.window_count(3).subscribe(move |w: DynObservable<YES, Ref<i32>>| {
+            let (out, out1) = out.clone().clones();
+            w.subscribe(move |v:&_| out.lock().unwrap().push_str(&format!("{}", v)), move |_e| out1.lock().unwrap().push_str(","));
+        }, ());
+
+        ::std::thread::spawn(move || {
+            for v in 0..7 { i.next(v); }
+            i.complete();
+        }).join().ok();
+
+        assert_eq!(out1.lock().unwrap().as_str(), "012,345,6,");
+    }
+}
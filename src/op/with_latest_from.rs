@@ -0,0 +1,327 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::cell::UnsafeCell;
+
+pub struct WithLatestFromOp<'o, SS:YesNo, VBy, Src, ByB: RefOrVal>
+{
+    src: Src,
+    other: DynObservable<'o, 'o, SS, ByB>,
+    PhantomData: PhantomData<AnySendSync<VBy>>
+}
+
+pub trait ObsWithLatestFromOp<'o, SS:YesNo, VBy: RefOrVal, ByB: RefOrVal> : Sized
+{
+    fn with_latest_from(self, other: impl Observable<'o, SS, ByB>+'o) -> WithLatestFromOp<'o, SS, VBy, Self, ByB>
+    {
+        WithLatestFromOp{ src: self, other: other.into_dyn(), PhantomData }
+    }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned, ByB: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+ObsWithLatestFromOp<'o, SS, VBy, ByB>
+for Src {}
+
+pub trait DynObsWithLatestFromOp<'o, SS:YesNo, VBy: IntoOwned, ByB: IntoOwned> : Sized
+{
+    fn with_latest_from(self, other: impl Observable<'o, SS, ByB>+'o) -> DynObservable<'o, 'o, SS, Val<(VBy::RAW, ByB::RAW)>>;
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, ByB: IntoOwned+'o>
+DynObsWithLatestFromOp<'o, SS, VBy, ByB>
+for DynObservable<'o, 'o, SS, VBy>
+where ByB::RAW: Clone
+{
+    fn with_latest_from(self, other: impl Observable<'o, SS, ByB>+'o) -> DynObservable<'o, 'o, SS, Val<(VBy::RAW, ByB::RAW)>>
+    {
+        WithLatestFromOp{ src: self.src, other: other.into_dyn(), PhantomData }.into_dyn()
+    }
+}
+
+//`ec` & `latest` are protected by `lock`
+struct WithLatestFromState<'o, SS:YesNo, N, EC, B>
+{
+    lock: ReSpinLock<SS>,
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    latest: UnsafeCell<Option<B>>,
+}
+
+impl<'o, SS:YesNo, N, EC: ActEc<'o, SS>, B> WithLatestFromState<'o, SS, N, EC, B>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.lock.enter();
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+        self.lock.exit();
+    }
+}
+
+impl<'o, SS:YesNo, VBy: IntoOwned+'o, ByB: IntoOwned+'o, Src: Observable<'o, SS, VBy>>
+Observable<'o, SS, Val<(VBy::RAW, ByB::RAW)>>
+for WithLatestFromOp<'o, SS, VBy, Src, ByB>
+where ByB::RAW: Clone
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<(VBy::RAW, ByB::RAW)>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(WithLatestFromState{
+            lock: ReSpinLock::new(), sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)), latest: UnsafeCell::new(None)
+        }) });
+
+        sub.add(self.other.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: ByB| {
+                let v = v.into_owned();
+                state.lock.enter();
+                let _drop = unsafe{ &mut *state.latest.get() }.replace(v);
+                state.lock.exit();
+            }, |(), state| state.sub.is_done()),
+
+            forward_ec(SSWrap::new(state.clone()), |state, e: Option<RxError>| {
+                if e.is_some() { state.end(e); }
+            })
+        ));
+
+        if sub.is_done() { return sub; }
+
+        sub.clone().added(self.src.subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: VBy| {
+                state.lock.enter();
+                let latest = unsafe{ &*state.latest.get() }.clone();
+                if let Some(b) = latest {
+                    if !state.sub.is_done() { state.next.call((v.into_owned(), b)); }
+                }
+                state.lock.exit();
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| state.end(e))
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<(VBy::RAW, ByB::RAW)>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+//samples the latest values of any number of sources of the source's type, emitting one `Vec` led by the source's value
+pub struct WithLatestFromAllOp<'o, SS:YesNo, By: RefOrVal>
+{
+    obs: Vec<DynObservable<'o, 'o, SS, By>>
+}
+
+pub trait ObsWithLatestFromAllOp<'o, SS:YesNo, By: RefOrVal> : Sized
+{
+    fn with_latest_from_all(self, others: impl Into<Vec<DynObservable<'o, 'o, SS, By>>>) -> WithLatestFromAllOp<'o, SS, By>;
+}
+
+impl<'o, SS:YesNo, By: IntoOwned+'o, Src: Observable<'o, SS, By>+'o>
+ObsWithLatestFromAllOp<'o, SS, By>
+for Src
+{
+    fn with_latest_from_all(self, others: impl Into<Vec<DynObservable<'o, 'o, SS, By>>>) -> WithLatestFromAllOp<'o, SS, By>
+    {
+        let mut obs = others.into();
+        obs.insert(0, self.into_dyn());
+        WithLatestFromAllOp{ obs }
+    }
+}
+
+pub trait DynObsWithLatestFromAllOp<'o, SS:YesNo, By: IntoOwned> : Sized
+{
+    fn with_latest_from_all(self, others: impl Into<Vec<DynObservable<'o, 'o, SS, By>>>) -> DynObservable<'o, 'o, SS, Val<Vec<By::RAW>>>;
+}
+
+impl<'o, SS:YesNo, By: IntoOwned+'o>
+DynObsWithLatestFromAllOp<'o, SS, By>
+for DynObservable<'o, 'o, SS, By>
+where By::RAW: Clone
+{
+    fn with_latest_from_all(self, others: impl Into<Vec<DynObservable<'o, 'o, SS, By>>>) -> DynObservable<'o, 'o, SS, Val<Vec<By::RAW>>>
+    {
+        let mut obs = others.into();
+        obs.insert(0, self);
+        WithLatestFromAllOp{ obs }.into_dyn()
+    }
+}
+
+//all fields but `sub` & `next` are protected by `sub`'s internal lock
+struct WithLatestFromAllState<'o, SS:YesNo, N, EC, V>
+{
+    sub: Unsub<'o, SS>,
+    next: N,
+    ec: UnsafeCell<Option<EC>>,
+    latest: UnsafeCell<Vec<Option<V>>>,
+}
+
+impl<'o, SS:YesNo, N: ActNext<'o, SS, Val<Vec<V>>>, EC: ActEc<'o, SS>, V: Clone> WithLatestFromAllState<'o, SS, N, EC, V>
+{
+    fn end(&self, e: Option<RxError>)
+    {
+        self.sub.unsub_then(|| unsafe{ &mut *self.ec.get() }.take().map_or((), |ec| ec.call_once(e)));
+    }
+
+    fn sample(&self, v: V)
+    {
+        let latest = unsafe{ &*self.latest.get() };
+        if latest.iter().all(|v| v.is_some()) {
+            let vs = ::std::iter::once(v).chain(latest.iter().map(|v| v.clone().unwrap())).collect();
+            self.next.call(vs);
+        }
+    }
+}
+
+impl<'o, SS:YesNo, By: IntoOwned+'o>
+Observable<'o, SS, Val<Vec<By::RAW>>>
+for WithLatestFromAllOp<'o, SS, By>
+where By::RAW: Clone
+{
+    fn subscribe(&self, next: impl ActNext<'o, SS, Val<Vec<By::RAW>>>, ec: impl ActEc<'o, SS>) -> Unsub<'o, SS> where Self: Sized
+    {
+        let sub = Unsub::new();
+        let state = Arc::new(unsafe{ AnySendSync::new(WithLatestFromAllState{
+            sub: sub.clone(), next, ec: UnsafeCell::new(Some(ec)),
+            latest: UnsafeCell::new(self.obs[1..].iter().map(|_| None).collect())
+        }) });
+
+        for (index, o) in self.obs[1..].iter().enumerate() {
+            if sub.is_done() { return sub; }
+
+            sub.add(o.subscribe(
+                forward_next((), SSWrap::new((state.clone(), index)), |(), caps, v: By| {
+                    let (state, index) = &**caps;
+                    let v = v.into_owned();
+                    state.sub.if_not_done(|| unsafe{ &mut *state.latest.get() }[*index] = Some(v));
+                }, |(), caps| caps.0.sub.is_done()),
+
+                forward_ec(SSWrap::new(state.clone()), |state, e: Option<RxError>| {
+                    if e.is_some() { state.sub.if_not_done(|| state.end(e)); }
+                })
+            ));
+        }
+
+        if sub.is_done() { return sub; }
+
+        sub.clone().added(self.obs[0].subscribe(
+            forward_next((), SSWrap::new(state.clone()), |(), state, v: By| {
+                state.sub.if_not_done(|| state.sample(v.into_owned()));
+            }, |(), state| state.sub.is_done() || state.next.stopped()),
+
+            forward_ec(SSWrap::new(state), |state, e: Option<RxError>| state.sub.if_not_done(|| state.end(e)))
+        ))
+    }
+
+    fn subscribe_dyn(&self, next: Box<ActNext<'o, SS, Val<Vec<By::RAW>>>>, ec: Box<ActEcBox<'o, SS>>) -> Unsub<'o, SS>
+    { self.subscribe(next, ec) }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use crate::util::clones::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+        let (a, a1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (b, b1) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        a.with_latest_from(b).subscribe(
+            |(a, b)| out.borrow_mut().push_str(&format!("{}{},", a, b)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        a1.next(1);
+        b1.next(1);
+        b1.next(2);
+        a1.next(2);
+        b1.complete();
+        a1.next(3);
+        assert_eq!(out.borrow().as_str(), "22,32,");
+
+        a1.complete();
+        assert_eq!(out.borrow().as_str(), "22,32,ok");
+    }
+
+    #[test]
+    fn behavior_subject()
+    {
+        let out = RefCell::new(String::new());
+        let (b, b1) = Rc::new(BehaviorSubject::<NO, String>::new("x".to_owned())).clones();
+
+        iter_clone(0..3).into_dyn().with_latest_from(b).subscribe(
+            |(a, b)| out.borrow_mut().push_str(&format!("{}{},", a, b)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        assert_eq!(out.borrow().as_str(), "0x,1x,2x,ok");
+        b1.next("y".to_owned());
+    }
+
+    #[test]
+    fn thread()
+    {
+        let (out, out1) = Arc::new(Mutex::new(String::new())).clones();
+        let (a, a1) = Arc::new(Subject::<YES, i32>::new()).clones();
+        let (b, b1) = Arc::new(BehaviorSubject::<YES, i32>::new(0)).clones();
+
+        a.with_latest_from(b).subscribe(move |(a, b)| out.lock().unwrap().push_str(&format!("{}{},", a, b)), ());
+
+        a1.next(1);
+        ::std::thread::spawn(move || b1.next(5)).join().ok();
+        a1.next(2);
+
+        assert_eq!(out1.lock().unwrap().as_str(), "10,25,");
+    }
+
+    #[test]
+    fn all()
+    {
+        let out = RefCell::new(String::new());
+        let (a, a1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (b, b1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (c, c1) = Rc::new(Subject::<NO, i32>::new()).clones();
+
+        a.with_latest_from_all(vec![b.into_dyn(), c.into_dyn()]).subscribe(
+            |vs: Vec<i32>| out.borrow_mut().push_str(&format!("{:?},", vs)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        a1.next(1);
+        b1.next(2);
+        a1.next(3);
+        c1.next(4);
+        a1.next(5);
+        b1.next(6);
+        c1.complete();
+        a1.next(7);
+        assert_eq!(out.borrow().as_str(), "[5, 2, 4],[7, 6, 4],");
+
+        a1.complete();
+        assert_eq!(out.borrow().as_str(), "[5, 2, 4],[7, 6, 4],ok");
+    }
+
+    #[test]
+    fn all_error()
+    {
+        let out = RefCell::new(String::new());
+        let (a, a1) = Rc::new(Subject::<NO, i32>::new()).clones();
+        let (b, b1) = Rc::new(BehaviorSubject::<NO, i32>::new(0)).clones();
+
+        a.into_dyn().with_latest_from_all(vec![b.into_dyn(), Of::value(1).into_dyn(), Of::value(2).into_dyn()]).subscribe(
+            |vs: Vec<i32>| out.borrow_mut().push_str(&format!("{:?},", vs)),
+            |e: Option<RxError>| out.borrow_mut().push_str(if e.map(|e| e.set_handled()).is_some() { "err" } else { "ok" })
+        );
+
+        a1.next(3);
+        b1.error(RxError::simple(None, "error"));
+        a1.next(4);
+        assert_eq!(out.borrow().as_str(), "[3, 0, 1, 2],err");
+    }
+}
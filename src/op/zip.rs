@@ -0,0 +1,80 @@
+use crate::*;
+
+pub trait ObsZipOp<'o, SS:YesNo, By: RefOrVal> : Sized
+{
+    fn zip<ByB: IntoOwned+'o>(self, other: impl Observable<'o, SS, ByB>+'o) -> Zip<'o, 'o, SS, By, ByB>;
+    fn zip_all(self, others: impl Into<Vec<DynObservable<'o, 'o, SS, By>>>) -> ZipAll<'o, 'o, SS, By>;
+}
+
+impl<'o, SS:YesNo, By: IntoOwned+'o, Src: Observable<'o, SS, By>+'o>
+ObsZipOp<'o, SS, By>
+for Src
+{
+    fn zip<ByB: IntoOwned+'o>(self, other: impl Observable<'o, SS, ByB>+'o) -> Zip<'o, 'o, SS, By, ByB>
+    {
+        Zip::new(self.into_dyn(), other.into_dyn())
+    }
+
+    fn zip_all(self, others: impl Into<Vec<DynObservable<'o, 'o, SS, By>>>) -> ZipAll<'o, 'o, SS, By>
+    {
+        let mut obs = others.into();
+        obs.insert(0, self.into_dyn());
+        ZipAll::new(obs)
+    }
+}
+
+pub trait DynObsZipOp<'o, SS:YesNo, By: IntoOwned> : Sized
+{
+    fn zip<ByB: IntoOwned+'o>(self, other: impl Observable<'o, SS, ByB>+'o) -> DynObservable<'o, 'o, SS, Val<(By::RAW, ByB::RAW)>>;
+    fn zip_all(self, others: impl Into<Vec<DynObservable<'o, 'o, SS, By>>>) -> DynObservable<'o, 'o, SS, Val<Vec<By::RAW>>>;
+}
+
+impl<'o, SS:YesNo, By: IntoOwned+'o>
+DynObsZipOp<'o, SS, By>
+for DynObservable<'o, 'o, SS, By>
+{
+    fn zip<ByB: IntoOwned+'o>(self, other: impl Observable<'o, SS, ByB>+'o) -> DynObservable<'o, 'o, SS, Val<(By::RAW, ByB::RAW)>>
+    {
+        Zip::new(self, other.into_dyn()).into_dyn()
+    }
+
+    fn zip_all(self, others: impl Into<Vec<DynObservable<'o, 'o, SS, By>>>) -> DynObservable<'o, 'o, SS, Val<Vec<By::RAW>>>
+    {
+        let mut obs = others.into();
+        obs.insert(0, self);
+        ZipAll::new(obs).into_dyn()
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn smoke()
+    {
+        let out = RefCell::new(String::new());
+
+        Of::value(1).zip(iter_clone(vec!["a", "b"].into_iter())).into_dyn().zip(Of::value(2.5)).subscribe(
+            |((a, b), c)| out.borrow_mut().push_str(&format!("{}{}{}", a, b, c)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        assert_eq!(out.borrow().as_str(), "1a2.5ok");
+    }
+
+    #[test]
+    fn all()
+    {
+        let out = RefCell::new(String::new());
+
+        iter_clone(0..3).zip_all(vec![iter_clone(3..6).into_dyn(), iter_clone(6..8).into_dyn()]).subscribe(
+            |vs: Vec<i32>| out.borrow_mut().push_str(&format!("{:?}", vs)),
+            |_e| out.borrow_mut().push_str("ok")
+        );
+
+        assert_eq!(out.borrow().as_str(), "[0, 3, 6][1, 4, 7]ok");
+    }
+}
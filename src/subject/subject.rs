@@ -176,7 +176,9 @@ impl<'o, V:'o+'o, SS:YesNo> Subject<'o, SS, V>
 
         state.map(|s:&UnsafeCell<_>|{
             if let Next(vec) = unsafe { &*s.get() } {
-                let _to_drop = state.replace(UnsafeCell::new(Error(e.clone())));
+                //the kept copy only replays to late subscribers, which get their own unhandled clone:
+                //it must not panic when the errored Subject is dropped
+                let _to_drop = state.replace(UnsafeCell::new(Error(e.clone().set_handled())));
         
                 for (_,ec,sub) in vec.iter() {
                     if sub.is_done() { continue; }
@@ -347,4 +349,28 @@ mod tests
         assert_eq!(n.get(), 123);
     }
 
+    #[test]
+    fn drop_after_error()
+    {
+        let n = Cell::new(0);
+        let s = Subject::<NO, i32>::new();
+        s.subscribe((), |e: Option<RxError>| { e.map(|e| e.set_handled()); n.replace(n.get() + 1); });
+
+        s.error(RxError::simple(None, "error"));
+        drop(s);
+
+        assert_eq!(n.get(), 1);
+    }
+
+    #[test]
+    fn late_subscriber_gets_error()
+    {
+        let n = Cell::new(0);
+        let s = Subject::<NO, i32>::new();
+        s.error(RxError::simple(None, "error"));
+
+        s.subscribe((), |e: Option<RxError>| { e.map(|e| e.set_handled()); n.replace(1); });
+        assert_eq!(n.get(), 1);
+    }
+
 }
\ No newline at end of file
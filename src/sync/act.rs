@@ -58,6 +58,20 @@ for F
     #[inline(always)] fn call(&self, v: *const V) -> R { self(unsafe{ &*v }) }
 }
 
+unsafe impl<'a, R, F: Fn() -> R+'a>
+Act<NO, (), R>
+for F
+{
+    #[inline(always)] fn call(&self, _v: ()) -> R { self() }
+}
+
+unsafe impl<'a, R, F: Fn()->R+Send+Sync+'a>
+Act<YES, (), R>
+for F
+{
+    #[inline(always)] fn call(&self, _v: ()) -> R { self() }
+}
+
 unsafe impl <'a, SS:YesNo, BY: RefOrVal, R>
 Act<SS, BY, R>
 for Box<Act<SS, BY, R>+'a>
@@ -66,6 +80,60 @@ for Box<Act<SS, BY, R>+'a>
 }
 
 
+pub unsafe trait ActFold<SS:YesNo, A: RefOrVal, Acc>
+{
+    fn call(&self, acc: Acc, v: A::V) -> Acc;
+}
+
+pub fn fold_sendsync<SS:YesNo, By: RefOrVal, Acc>(act: impl ActFold<SS, By, Acc>) -> impl ActFold<SS, By, Acc> + Ssmark<SS>
+{
+    struct X<A>(A);
+    unsafe impl<SS:YesNo, A> Ssmark<SS> for X<A>{}
+    unsafe impl<SS: YesNo, By:RefOrVal, Acc, A: ActFold<SS, By, Acc>> ActFold<SS, By, Acc> for X<A>
+    {
+        #[inline(always)]
+        fn call(&self, acc: Acc, v: <By as RefOrVal>::V) -> Acc { self.0.call(acc, v) }
+    }
+
+    X(act)
+}
+
+unsafe impl<'a, V, Acc, F: Fn(Acc, V) -> Acc+'a>
+ActFold<NO, Val<V>, Acc>
+for F
+{
+    #[inline(always)] fn call(&self, acc: Acc, v: V) -> Acc { self(acc, v) }
+}
+
+unsafe impl<'a, V, Acc, F: Fn(Acc, V) -> Acc+Send+Sync+'a>
+ActFold<YES, Val<V>, Acc>
+for F
+{
+    #[inline(always)] fn call(&self, acc: Acc, v: V) -> Acc { self(acc, v) }
+}
+
+unsafe impl<'a, V, Acc, F: Fn(Acc, &V) -> Acc+'a>
+ActFold<NO, Ref<V>, Acc>
+for F
+{
+    #[inline(always)] fn call(&self, acc: Acc, v: *const V) -> Acc { self(acc, unsafe{ &*v }) }
+}
+
+unsafe impl<'a, V, Acc, F: Fn(Acc, &V) -> Acc+Send+Sync+'a>
+ActFold<YES, Ref<V>, Acc>
+for F
+{
+    #[inline(always)] fn call(&self, acc: Acc, v: *const V) -> Acc { self(acc, unsafe{ &*v }) }
+}
+
+unsafe impl<SS:YesNo, By: RefOrVal, Acc, A: ActFold<SS, By, Acc>>
+ActFold<SS, By, Acc>
+for Arc<A>
+{
+    #[inline(always)] fn call(&self, acc: Acc, v: By::V) -> Acc { Arc::as_ref(self).call(acc, v) }
+}
+
+
 
 
 unsafe impl<'a, V, R, F: FnOnce(V) -> R+'a>
@@ -151,19 +219,19 @@ for ()
 }
 
 
-pub fn forward_act_once<'o, SS:YesNo, Caps: Ssmark<SS>+'o, BY: RefOrVal>
-(caps: Caps, f: fn(Caps, BY))
-    -> SsForward<SS, (Caps, fn(Caps, BY))>
+pub fn forward_act_once<'o, SS:YesNo, Caps: Ssmark<SS>+'o, BY: RefOrVal, R>
+(caps: Caps, f: fn(Caps, BY) -> R)
+    -> SsForward<SS, (Caps, fn(Caps, BY) -> R)>
 {
     SsForward::new((caps, f))
 }
 
-unsafe impl<'o, SS:YesNo, Caps: Ssmark<SS>+'o, BY: RefOrVal>
-ActOnce<SS, BY>
-for SsForward<SS, (Caps, fn(Caps, BY))>
+unsafe impl<'o, SS:YesNo, Caps: Ssmark<SS>+'o, BY: RefOrVal, R>
+ActOnce<SS, BY, R>
+for SsForward<SS, (Caps, fn(Caps, BY) -> R)>
 {
     #[inline(always)]
-    fn call_once(self, v: BY::V)
+    fn call_once(self, v: BY::V) -> R
     {
         let (caps, f) = self.captures;
         f(caps,  unsafe{ BY::from_v(v) })